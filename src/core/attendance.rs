@@ -1,4 +1,4 @@
-use super::{Position, Subject, Table};
+use super::{CellKind, Position, Subject, Table};
 use crate::utilities::SystemTimeExt;
 use std::{collections::HashMap, time::SystemTime};
 
@@ -25,49 +25,49 @@ impl AttendanceStatus {
         }
     }
 
-    pub const fn css_class(self) -> &'static str {
-        match self {
-            Self::Checked => "status-checked",
-            Self::Unchecked => "status-unchecked",
-            Self::Marked => "status-marked",
-        }
-    }
-
-    pub const fn background_color(self) -> &'static str {
+    /// Shown next to names, so the status can be told apart without its color.
+    pub const fn symbol(self) -> &'static str {
         match self {
-            Self::Checked => "#22c55e",
-            Self::Unchecked => "#ff0000",
-            Self::Marked => "#facc15",
+            Self::Checked => "✓",
+            Self::Unchecked => "✗",
+            Self::Marked => "○",
         }
     }
 
-    pub const fn background_alpha(self) -> f32 {
+    /// Lowercase identifier used in the REST API and other machine-readable output.
+    pub const fn api_name(self) -> &'static str {
         match self {
-            Self::Checked => 0.45,
-            Self::Unchecked => 0.45,
-            Self::Marked => 0.45,
+            Self::Checked => "checked",
+            Self::Unchecked => "unchecked",
+            Self::Marked => "marked",
         }
     }
 
-    pub const fn background_rgb(self) -> (u8, u8, u8) {
-        match self {
-            Self::Checked => (34, 197, 94),
-            Self::Unchecked => (239, 68, 68),
-            Self::Marked => (250, 204, 21),
-        }
+    pub fn from_api_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|status| status.api_name().eq_ignore_ascii_case(name.trim()))
     }
 
-    pub const fn foreground_color(self) -> &'static str {
+    pub const fn css_class(self) -> &'static str {
         match self {
-            Self::Checked => "#052e16",
-            Self::Unchecked => "#451010",
-            Self::Marked => "#422006",
+            Self::Checked => "status-checked",
+            Self::Unchecked => "status-unchecked",
+            Self::Marked => "status-marked",
         }
     }
 }
 
+/// Default Chinese statistics export, see [`AttendanceBook::build_export_text`].
+pub const EXPORT_TEMPLATE_ZH: &str =
+    "{time}({period})\n[未签到 {unchecked_count}人 已签到{completed_percent}%]\n{unchecked_names_by_group}\n[请假 {marked_count}人]\n{marked_names}";
+
+/// Default English statistics export, see [`AttendanceBook::build_export_text`].
+pub const EXPORT_TEMPLATE_EN: &str =
+    "{time}\n[Unchecked: {unchecked_count}, {completed_percent}% checked in]\n{unchecked_names_by_group}\n[On leave: {marked_count}]\n{marked_names}";
+
 /// Aggregated attendance metrics for the current table snapshot.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct AttendanceStatistics {
     pub checked: u32,
     pub unchecked: u32,
@@ -104,6 +104,38 @@ impl AttendanceStatistics {
 #[derive(Debug, Clone, Default)]
 pub struct AttendanceBook {
     statuses: HashMap<Position, AttendanceStatus>,
+    /// When each currently `Checked` seat was checked in.
+    checked_at: HashMap<Position, SystemTime>,
+    /// Free-text notes, e.g. the reason for a leave.
+    notes: HashMap<Position, String>,
+    /// Running totals of `statuses`, so statistics don't walk the table.
+    counts: StatusCounts,
+}
+
+/// Number of seats in each status.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct StatusCounts {
+    checked: u32,
+    unchecked: u32,
+    marked: u32,
+}
+
+impl StatusCounts {
+    fn slot(&mut self, status: AttendanceStatus) -> &mut u32 {
+        match status {
+            AttendanceStatus::Checked => &mut self.checked,
+            AttendanceStatus::Unchecked => &mut self.unchecked,
+            AttendanceStatus::Marked => &mut self.marked,
+        }
+    }
+
+    fn add(&mut self, status: AttendanceStatus) {
+        *self.slot(status) += 1;
+    }
+
+    fn remove(&mut self, status: AttendanceStatus) {
+        *self.slot(status) -= 1;
+    }
 }
 
 impl AttendanceBook {
@@ -115,26 +147,77 @@ impl AttendanceBook {
             }
         }
 
-        Self { statuses }
+        Self {
+            counts: StatusCounts {
+                unchecked: statuses.len() as u32,
+                ..StatusCounts::default()
+            },
+            statuses,
+            checked_at: HashMap::new(),
+            notes: HashMap::new(),
+        }
     }
 
     pub fn status_at(&self, position: Position) -> Option<AttendanceStatus> {
         self.statuses.get(&position).copied()
     }
 
+    /// Time the seat was last set to `Checked`, while it stays `Checked`.
+    pub fn checked_at(&self, position: Position) -> Option<SystemTime> {
+        self.checked_at.get(&position).copied()
+    }
+
+    /// Note on a seat, e.g. why the person is on leave.
+    pub fn note_at(&self, position: Position) -> Option<&str> {
+        self.notes.get(&position).map(String::as_str)
+    }
+
+    /// Sets or, with `None` or blank text, clears the note on an active seat.
+    /// Returns `true` only when the note changed.
+    pub fn set_note(&mut self, table: &Table, position: Position, note: Option<String>) -> bool {
+        if !table.contains(position) || table.is_inert(position) {
+            return false;
+        }
+        let note = note
+            .map(|note| note.trim().to_owned())
+            .filter(|note| !note.is_empty());
+        match note {
+            Some(note) if self.note_at(position) == Some(note.as_str()) => false,
+            Some(note) => {
+                self.notes.insert(position, note);
+                true
+            }
+            None => self.notes.remove(&position).is_some(),
+        }
+    }
+
     /// Ensures attendance entries match current table kinds after table edits.
     pub fn reconcile_with_table(&mut self, table: &Table) {
-        self.statuses
-            .retain(|position, _| table.contains(*position) && !table.is_inert(*position));
+        let counts = &mut self.counts;
+        self.statuses.retain(|position, status| {
+            let keep = table.contains(*position) && !table.is_inert(*position);
+            if !keep {
+                counts.remove(*status);
+            }
+            keep
+        });
+        let statuses = &self.statuses;
+        self.checked_at
+            .retain(|position, _| statuses.contains_key(position));
+        self.notes
+            .retain(|position, _| statuses.contains_key(position));
 
         for position in table.iter_positions() {
-            if !table.is_inert(position) {
-                self.statuses.entry(position).or_default();
+            if !table.is_inert(position) && !self.statuses.contains_key(&position) {
+                self.statuses.insert(position, AttendanceStatus::Unchecked);
+                self.counts.add(AttendanceStatus::Unchecked);
             }
         }
     }
 
     /// Updates status for an active seat. Returns `true` only when a real change happened.
+    ///
+    /// A real change clears the seat's note, which belonged to the old status.
     pub fn update_status(
         &mut self,
         table: &Table,
@@ -145,57 +228,145 @@ impl AttendanceBook {
             return false;
         }
 
-        let current = self.statuses.entry(position).or_default();
-        if *current == next_status {
+        let current = match self.statuses.get(&position) {
+            Some(current) => *current,
+            None => {
+                self.statuses.insert(position, AttendanceStatus::Unchecked);
+                self.counts.add(AttendanceStatus::Unchecked);
+                AttendanceStatus::Unchecked
+            }
+        };
+        if current == next_status {
             return false;
         }
 
-        *current = next_status;
+        self.statuses.insert(position, next_status);
+        self.notes.remove(&position);
+        self.counts.remove(current);
+        self.counts.add(next_status);
+        if next_status == AttendanceStatus::Checked {
+            self.checked_at.insert(position, SystemTime::now());
+        } else {
+            self.checked_at.remove(&position);
+        }
         true
     }
 
+    /// Statistics from the running counters; expects the book to be reconciled with
+    /// `table`. Debug builds check the counters against a full recount.
     pub fn statistics(&self, table: &Table) -> AttendanceStatistics {
-        let mut checked = 0;
-        let mut unchecked = 0;
-        let mut marked = 0;
+        let statistics = AttendanceStatistics {
+            checked: self.counts.checked,
+            unchecked: self.counts.unchecked,
+            marked: self.counts.marked,
+            active_total: table.active_cells(),
+            blocked_total: table.blocked_cells(),
+        };
+        debug_assert_eq!(
+            statistics,
+            AttendanceStatistics {
+                active_total: table.active_cells(),
+                blocked_total: table.blocked_cells(),
+                ..self.statistics_over(table, table.iter_positions())
+            },
+            "attendance counters drifted from a full recount"
+        );
+        statistics
+    }
 
-        for position in table.iter_positions() {
-            if table.is_inert(position) {
-                continue;
+    /// Statistics for every group in the table, sorted by group name.
+    pub fn group_statistics(&self, table: &Table) -> Vec<(String, AttendanceStatistics)> {
+        table
+            .group_names()
+            .into_iter()
+            .map(|group| {
+                let statistics =
+                    self.statistics_over(table, table.positions_in_group(&group).into_iter());
+                (group, statistics)
+            })
+            .collect()
+    }
+
+    fn statistics_over(
+        &self,
+        table: &Table,
+        positions: impl Iterator<Item = Position>,
+    ) -> AttendanceStatistics {
+        let mut statistics = AttendanceStatistics::default();
+        for position in positions {
+            match table.cell_kind(position) {
+                Some(CellKind::Active) => statistics.active_total += 1,
+                Some(CellKind::Blocked) => {
+                    statistics.blocked_total += 1;
+                    continue;
+                }
+                _ => continue,
             }
 
             match self.status_at(position).unwrap_or_default() {
-                AttendanceStatus::Checked => checked += 1,
-                AttendanceStatus::Unchecked => unchecked += 1,
-                AttendanceStatus::Marked => marked += 1,
+                AttendanceStatus::Checked => statistics.checked += 1,
+                AttendanceStatus::Unchecked => statistics.unchecked += 1,
+                AttendanceStatus::Marked => statistics.marked += 1,
             }
         }
-
-        AttendanceStatistics {
-            checked,
-            unchecked,
-            marked,
-            active_total: table.active_cells(),
-            blocked_total: table.blocked_cells(),
-        }
+        statistics
     }
 
     /// Builds a Chinese export string for sharing check-in progress.
     pub fn build_export_text_zh(&self, table: &Table, time: &SystemTime) -> String {
+        self.build_export_text(table, time, EXPORT_TEMPLATE_ZH)
+    }
+
+    /// Fills an export template for sharing check-in progress.
+    ///
+    /// Supported placeholders: `{time}`, `{period}`, `{checked_count}`,
+    /// `{unchecked_count}`, `{marked_count}`, `{completed_percent}`, `{checked_names}`,
+    /// `{unchecked_names}`, `{unchecked_names_by_group}`, `{unchecked_seats}` and
+    /// `{marked_names}`. Unknown placeholders are kept verbatim.
+    ///
+    /// `{unchecked_names_by_group}` puts each group's absentees on a `group: names` line,
+    /// after the ungrouped ones. Without groups it matches `{unchecked_names}`.
+    /// `{unchecked_seats}` adds each absentee's seat, e.g. `Bob (Row A, Window side)`.
+    /// Names carry their note, e.g. `Bob (sick)` or `Bob (Row A, Window side; sick)`.
+    pub fn build_export_text(&self, table: &Table, time: &SystemTime, template: &str) -> String {
         let statistics = self.statistics(table);
-        let time = format!("{}({})", time.formatted_string(), time.period_string());
-        let unchecked_names = self.names_by_status(table, AttendanceStatus::Unchecked);
-        let marked_names = self.names_by_status(table, AttendanceStatus::Marked);
+        let values = [
+            ("{time}", time.formatted_string()),
+            ("{period}", time.period_string().to_owned()),
+            ("{checked_count}", statistics.checked.to_string()),
+            ("{unchecked_count}", statistics.unchecked.to_string()),
+            ("{marked_count}", statistics.marked.to_string()),
+            (
+                "{completed_percent}",
+                statistics.completed_ratio_percent().to_string(),
+            ),
+            (
+                "{checked_names}",
+                Self::format_names(&self.names_by_status(table, AttendanceStatus::Checked)),
+            ),
+            (
+                "{unchecked_names}",
+                Self::format_names(&self.names_by_status(table, AttendanceStatus::Unchecked)),
+            ),
+            (
+                "{unchecked_names_by_group}",
+                self.unchecked_names_by_group(table),
+            ),
+            (
+                "{unchecked_seats}",
+                Self::format_names(&self.seats_by_status(table, AttendanceStatus::Unchecked)),
+            ),
+            (
+                "{marked_names}",
+                Self::format_names(&self.names_by_status(table, AttendanceStatus::Marked)),
+            ),
+        ];
 
-        format!(
-            "{}\n[未签到 {}人 已签到{}%]\n{}\n[请假 {}人]\n{}",
-            time,
-            statistics.unchecked,
-            statistics.completed_ratio_percent(),
-            Self::format_names(&unchecked_names),
-            statistics.marked,
-            Self::format_names(&marked_names),
-        )
+        let mut text = template.to_owned();
+        for (placeholder, value) in values {
+            text = text.replace(placeholder, &value);
+        }
+        text
     }
 
     fn names_by_status(&self, table: &Table, status: AttendanceStatus) -> Vec<String> {
@@ -206,12 +377,70 @@ impl AttendanceBook {
             }
 
             if let Some(Subject::Some(name)) = table.subject_at(position) {
-                names.push(name.clone());
+                names.push(self.name_with_note(name, position));
             }
         }
         names
     }
 
+    fn seats_by_status(&self, table: &Table, status: AttendanceStatus) -> Vec<String> {
+        let mut seats = Vec::new();
+        for position in table.iter_positions() {
+            if self.status_at(position) != Some(status) {
+                continue;
+            }
+
+            if let Some(Subject::Some(name)) = table.subject_at(position) {
+                let seat = table.seat_label(position);
+                seats.push(match self.note_at(position) {
+                    Some(note) => format!("{name} ({seat}; {note})"),
+                    None => format!("{name} ({seat})"),
+                });
+            }
+        }
+        seats
+    }
+
+    fn unchecked_names_by_group(&self, table: &Table) -> String {
+        let is_unchecked =
+            |position: &Position| self.status_at(*position) == Some(AttendanceStatus::Unchecked);
+        let name_at = |position: Position| match table.subject_at(position) {
+            Some(Subject::Some(name)) => Some(self.name_with_note(name, position)),
+            _ => None,
+        };
+
+        let ungrouped: Vec<String> = table
+            .iter_positions()
+            .filter(|position| table.groups_at(*position).is_empty())
+            .filter(is_unchecked)
+            .filter_map(name_at)
+            .collect();
+        let mut lines = Vec::new();
+        if !ungrouped.is_empty() {
+            lines.push(Self::format_names(&ungrouped));
+        }
+        for group in table.group_names() {
+            let names: Vec<String> = table
+                .positions_in_group(&group)
+                .into_iter()
+                .filter(is_unchecked)
+                .filter_map(name_at)
+                .collect();
+            if !names.is_empty() {
+                lines.push(format!("{group}: {}", Self::format_names(&names)));
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// `name (note)`, or just the name without a note.
+    fn name_with_note(&self, name: &str, position: Position) -> String {
+        match self.note_at(position) {
+            Some(note) => format!("{name} ({note})"),
+            None => name.to_owned(),
+        }
+    }
+
     fn format_names(names: &[String]) -> String {
         if names.is_empty() {
             String::from("")
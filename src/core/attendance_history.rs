@@ -0,0 +1,187 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+
+use super::{SessionRecord, SessionStatus};
+
+/// One person's attendance across stored sessions.
+///
+/// People are identified by name, since seats change between sessions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PersonAttendance {
+    pub name: String,
+    pub sessions: u32,
+    pub present: u32,
+    pub absent: u32,
+    pub leave: u32,
+    pub late: u32,
+}
+
+impl PersonAttendance {
+    /// Sessions the person showed up for, late or not.
+    pub const fn attended(&self) -> u32 {
+        self.present + self.late
+    }
+
+    /// Share of sessions attended.
+    pub const fn attendance_percent(&self) -> u32 {
+        match (self.attended() * 100).checked_div(self.sessions) {
+            Some(percent) => percent,
+            None => 0,
+        }
+    }
+
+    fn record(&mut self, status: SessionStatus) {
+        self.sessions += 1;
+        match status {
+            SessionStatus::Present => self.present += 1,
+            SessionStatus::Absent => self.absent += 1,
+            SessionStatus::Leave => self.leave += 1,
+            SessionStatus::Late => self.late += 1,
+        }
+    }
+}
+
+/// Aggregates per-person attendance over sessions dated within `from..=to`.
+///
+/// Either bound may be open. Sessions without a readable date are only included when
+/// no bound is set. Results are sorted by name.
+pub fn person_attendance(
+    records: &[SessionRecord],
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Vec<PersonAttendance> {
+    let mut people: BTreeMap<String, PersonAttendance> = BTreeMap::new();
+    let in_range = |record: &SessionRecord| match record.date() {
+        Some(date) => from.is_none_or(|from| date >= from) && to.is_none_or(|to| date <= to),
+        None => from.is_none() && to.is_none(),
+    };
+
+    for record in records.iter().filter(|record| in_range(record)) {
+        for entry in &record.entries {
+            people
+                .entry(entry.name.clone())
+                .or_insert_with(|| PersonAttendance {
+                    name: entry.name.clone(),
+                    ..PersonAttendance::default()
+                })
+                .record(entry.status);
+        }
+    }
+    people.into_values().collect()
+}
+
+/// Sessions compared on each side when looking for a downward trend.
+const SLIP_WINDOW: usize = 3;
+/// Drop in average completion, in percentage points, that counts as slipping.
+const SLIP_THRESHOLD: u32 = 5;
+
+/// Completion of one stored session, for trend charts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionCompletion {
+    pub date: Option<NaiveDate>,
+    pub percent: u32,
+}
+
+/// Completion of the last `count` sessions, oldest first.
+pub fn recent_completion(records: &[SessionRecord], count: usize) -> Vec<SessionCompletion> {
+    records[records.len().saturating_sub(count)..]
+        .iter()
+        .map(|record| SessionCompletion {
+            date: record.date(),
+            percent: record.completion_percent(),
+        })
+        .collect()
+}
+
+/// Whether the latest sessions average clearly below the ones just before them.
+pub fn is_slipping(completions: &[SessionCompletion]) -> bool {
+    if completions.len() < SLIP_WINDOW * 2 {
+        return false;
+    }
+    let average = |window: &[SessionCompletion]| {
+        window
+            .iter()
+            .map(|completion| completion.percent)
+            .sum::<u32>()
+            / window.len() as u32
+    };
+    let (earlier, latest) =
+        completions[completions.len() - SLIP_WINDOW * 2..].split_at(SLIP_WINDOW);
+    average(latest) + SLIP_THRESHOLD <= average(earlier)
+}
+
+/// Column of the per-person attendance table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PersonAttendanceColumn {
+    #[default]
+    Name,
+    Sessions,
+    Present,
+    Absent,
+    Leave,
+    Late,
+    Percent,
+}
+
+impl PersonAttendanceColumn {
+    pub const ALL: [Self; 7] = [
+        Self::Name,
+        Self::Sessions,
+        Self::Present,
+        Self::Absent,
+        Self::Leave,
+        Self::Late,
+        Self::Percent,
+    ];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Sessions => "sessions",
+            Self::Present => "present",
+            Self::Absent => "absent",
+            Self::Leave => "leave",
+            Self::Late => "late",
+            Self::Percent => "attendance %",
+        }
+    }
+
+    pub fn cell_text(self, person: &PersonAttendance) -> String {
+        match self {
+            Self::Name => person.name.clone(),
+            Self::Sessions => person.sessions.to_string(),
+            Self::Present => person.present.to_string(),
+            Self::Absent => person.absent.to_string(),
+            Self::Leave => person.leave.to_string(),
+            Self::Late => person.late.to_string(),
+            Self::Percent => format!("{}%", person.attendance_percent()),
+        }
+    }
+
+    /// Sorts by this column, breaking ties by name.
+    pub fn sort(self, people: &mut [PersonAttendance], descending: bool) {
+        people.sort_by(|left, right| {
+            let ordering = self.compare(left, right);
+            let ordering = if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+            ordering.then_with(|| left.name.cmp(&right.name))
+        });
+    }
+
+    fn compare(self, left: &PersonAttendance, right: &PersonAttendance) -> Ordering {
+        match self {
+            Self::Name => left.name.to_lowercase().cmp(&right.name.to_lowercase()),
+            Self::Sessions => left.sessions.cmp(&right.sessions),
+            Self::Present => left.present.cmp(&right.present),
+            Self::Absent => left.absent.cmp(&right.absent),
+            Self::Leave => left.leave.cmp(&right.leave),
+            Self::Late => left.late.cmp(&right.late),
+            Self::Percent => left.attendance_percent().cmp(&right.attendance_percent()),
+        }
+    }
+}
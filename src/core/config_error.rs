@@ -0,0 +1,52 @@
+use std::fmt;
+use std::io;
+
+/// Failure while reading or writing the table config file.
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Parse(serde_json::Error),
+    /// The file was written by a newer build that uses a schema this binary doesn't know.
+    UnsupportedVersion {
+        found: u32,
+        supported: u32,
+    },
+    /// The JSON is valid but matches none of the known config layouts.
+    UnrecognizedFormat,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Parse(error) => write!(f, "invalid config JSON: {error}"),
+            Self::UnsupportedVersion { found, supported } => write!(
+                f,
+                "config version {found} is newer than supported version {supported}, please upgrade checkin"
+            ),
+            Self::UnrecognizedFormat => write!(f, "unrecognized config format"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Parse(error) => Some(error),
+            Self::UnsupportedVersion { .. } | Self::UnrecognizedFormat => None,
+        }
+    }
+}
+
+impl From<io::Error> for ConfigError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for ConfigError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Parse(error)
+    }
+}
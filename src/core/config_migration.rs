@@ -0,0 +1,125 @@
+use serde_json::{json, Map, Value};
+
+use super::{ConfigError, SaveData};
+
+/// Schema version written by this build.
+pub const CONFIG_VERSION: u32 = ANNOTATED;
+
+/// v0: a table config object at the top level.
+const BARE: u32 = 0;
+/// v1: `{ "default_table": ... }`.
+const WRAPPED: u32 = 1;
+/// v2: the wrapped layout with an explicit `version`.
+const VERSIONED: u32 = 2;
+/// v3: adds seat `groups`, `row_labels`/`column_labels` and `merged_cells`, which v2
+/// readers would silently drop on save.
+const ANNOTATED: u32 = 3;
+
+/// Known on-disk layouts, oldest first.
+///
+/// - `LegacySaveData`: the old attendance export (`table.colomn_count`, `attendances`).
+/// - `Bare` (v0): a table config object at the top level.
+/// - `Wrapped` (v1): `{ "default_table": ... }` without a version field.
+/// - `Versioned(n)` (v2+): the wrapped layout with an explicit `version`; v3 files may
+///   also carry groups, labels and merged cells.
+///
+/// Files that state `"version": 0` or `1` are read as `Bare` or `Wrapped`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SchemaVersion {
+    LegacySaveData,
+    Bare,
+    Wrapped,
+    Versioned(u32),
+}
+
+/// Upgrades a parsed config document step by step to [`CONFIG_VERSION`].
+pub(super) fn migrate_to_current(mut value: Value) -> Result<Value, ConfigError> {
+    loop {
+        value = match detect_version(&value)? {
+            SchemaVersion::LegacySaveData => migrate_legacy_save_data(value)?,
+            SchemaVersion::Bare => migrate_bare_to_wrapped(value),
+            SchemaVersion::Wrapped => migrate_wrapped_to_versioned(value),
+            SchemaVersion::Versioned(VERSIONED) => set_version(value, ANNOTATED),
+            SchemaVersion::Versioned(CONFIG_VERSION) => return Ok(value),
+            SchemaVersion::Versioned(found) => {
+                return Err(ConfigError::UnsupportedVersion {
+                    found,
+                    supported: CONFIG_VERSION,
+                })
+            }
+        };
+    }
+}
+
+fn detect_version(value: &Value) -> Result<SchemaVersion, ConfigError> {
+    let Some(object) = value.as_object() else {
+        return Err(ConfigError::UnrecognizedFormat);
+    };
+
+    if let Some(version) = object.get("version") {
+        let version = version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or(ConfigError::UnrecognizedFormat)?;
+        return Ok(match version {
+            BARE => SchemaVersion::Bare,
+            WRAPPED => SchemaVersion::Wrapped,
+            version => SchemaVersion::Versioned(version),
+        });
+    }
+    if object.contains_key("default_table") {
+        return Ok(SchemaVersion::Wrapped);
+    }
+    if object.contains_key("attendances") && object.contains_key("table") {
+        return Ok(SchemaVersion::LegacySaveData);
+    }
+    if object.contains_key("row_count") && object.contains_key("column_count") {
+        return Ok(SchemaVersion::Bare);
+    }
+    Err(ConfigError::UnrecognizedFormat)
+}
+
+/// Legacy save data only carries seat names; the `marked` attendance indices are not
+/// layout data and are dropped.
+fn migrate_legacy_save_data(value: Value) -> Result<Value, ConfigError> {
+    let save: SaveData = serde_json::from_value(value)?;
+    let subjects: Vec<Value> = save
+        .attendances
+        .into_iter()
+        .map(|attendance| {
+            json!({
+                "x": attendance.position.x,
+                "y": attendance.position.y,
+                "kind": "active",
+                "name": attendance.name,
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "row_count": save.table.row_count,
+        "column_count": save.table.colomn_count,
+        "subjects": subjects,
+    }))
+}
+
+fn migrate_bare_to_wrapped(mut value: Value) -> Value {
+    if let Value::Object(object) = &mut value {
+        object.remove("version");
+    }
+    json!({ "default_table": value })
+}
+
+fn migrate_wrapped_to_versioned(value: Value) -> Value {
+    set_version(value, VERSIONED)
+}
+
+/// Steps that only add optional fields just restate the version.
+fn set_version(value: Value, version: u32) -> Value {
+    let mut object = match value {
+        Value::Object(object) => object,
+        _ => Map::new(),
+    };
+    object.insert(String::from("version"), json!(version));
+    Value::Object(object)
+}
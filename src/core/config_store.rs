@@ -0,0 +1,182 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+
+/// Replaces `path` with `contents` without ever exposing a partially written file.
+///
+/// Data goes to a sibling temp file that is flushed to disk and then renamed over the
+/// target, so a crash leaves either the old or the new file in place.
+pub fn write_file_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    write_atomic(path, contents, false)
+}
+
+/// Like [`write_file_atomic`], but only the owner may read the file (mode 0600 on Unix),
+/// for secrets such as the REST API token.
+pub fn write_private_file_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    write_atomic(path, contents, true)
+}
+
+fn write_atomic(path: &Path, contents: &[u8], private: bool) -> io::Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let result = (|| {
+        let mut file = create_temp_file(&temp_path, private)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+        return result;
+    }
+
+    // Persist the rename itself; not every platform supports syncing directories.
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        if let Ok(dir) = fs::File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+    Ok(())
+}
+
+/// The rename keeps the temp file's permissions, so a private file is private from the
+/// start. A leftover temp file is removed first, as its mode would otherwise be kept.
+fn create_temp_file(path: &Path, private: bool) -> io::Result<fs::File> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    if private {
+        match fs::remove_file(path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error),
+            _ => {}
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+    }
+    options.open(path)
+}
+
+/// Timestamped copies of a config file, pruned to the newest `keep` entries.
+///
+/// Backups are named `<stem>.<timestamp>.<ext>` so lexical order is chronological.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigBackups {
+    dir: PathBuf,
+    keep: usize,
+}
+
+impl ConfigBackups {
+    pub fn new(dir: PathBuf, keep: usize) -> Self {
+        Self { dir, keep }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub const fn keep(&self) -> usize {
+        self.keep
+    }
+
+    /// Copies the current `config_file` into the backup directory, then prunes old copies.
+    ///
+    /// Returns `None` when backups are disabled or there is no file to back up yet.
+    pub fn backup(&self, config_file: &Path) -> io::Result<Option<PathBuf>> {
+        if self.keep == 0 || !config_file.exists() {
+            return Ok(None);
+        }
+
+        fs::create_dir_all(&self.dir)?;
+        let (stem, extension) = Self::name_parts(config_file);
+        let timestamp = Local::now().format("%Y%m%d-%H%M%S%.3f");
+        let backup = self.dir.join(format!("{stem}.{timestamp}.{extension}"));
+        let contents = fs::read(config_file)?;
+        write_file_atomic(&backup, &contents)?;
+
+        self.prune(config_file)?;
+        Ok(Some(backup))
+    }
+
+    /// Lists backups of `config_file`, newest first.
+    pub fn list(&self, config_file: &Path) -> io::Result<Vec<PathBuf>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+
+        let (stem, extension) = Self::name_parts(config_file);
+        let prefix = format!("{stem}.");
+        let suffix = format!(".{extension}");
+        let mut backups = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if name.starts_with(&prefix)
+                && name.ends_with(&suffix)
+                && name.len() > prefix.len() + suffix.len()
+            {
+                backups.push(path);
+            }
+        }
+
+        backups.sort();
+        backups.reverse();
+        Ok(backups)
+    }
+
+    /// Atomically copies `backup` over `config_file`.
+    ///
+    /// The replaced file may still hold edits, so it is first copied to
+    /// `<name>.broken-<timestamp>` next to it; the original stays in place until the
+    /// backup replaces it. Returns the copy's path, if there was a file.
+    pub fn restore(&self, backup: &Path, config_file: &Path) -> io::Result<Option<PathBuf>> {
+        let contents = fs::read(backup)?;
+        let kept = if config_file.exists() {
+            let timestamp = Local::now().format("%Y%m%d-%H%M%S");
+            let mut name = config_file.file_name().unwrap_or_default().to_owned();
+            name.push(format!(".broken-{timestamp}"));
+            let kept = config_file.with_file_name(name);
+            fs::copy(config_file, &kept)?;
+            Some(kept)
+        } else {
+            None
+        };
+        write_file_atomic(config_file, &contents)?;
+        Ok(kept)
+    }
+
+    fn prune(&self, config_file: &Path) -> io::Result<()> {
+        for stale in self.list(config_file)?.into_iter().skip(self.keep) {
+            fs::remove_file(stale)?;
+        }
+        Ok(())
+    }
+
+    fn name_parts(config_file: &Path) -> (String, String) {
+        let stem = config_file.file_stem().map_or_else(
+            || String::from("config"),
+            |stem| stem.to_string_lossy().into_owned(),
+        );
+        let extension = config_file.extension().map_or_else(
+            || String::from("bak"),
+            |ext| ext.to_string_lossy().into_owned(),
+        );
+        (stem, extension)
+    }
+}
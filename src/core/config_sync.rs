@@ -0,0 +1,54 @@
+use super::Table;
+
+/// Outcome of comparing a freshly read config against the running layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalChange {
+    /// The file matches what the app already knows about, e.g. after its own write.
+    Unchanged,
+    /// Only the file changed; it can be reloaded without losing local edits.
+    Reloadable(Table),
+    /// Both the file and the running layout changed since the last sync.
+    Conflict(Table),
+}
+
+/// Tracks the layout last loaded from or written to the config file.
+///
+/// Comparing the on-disk table and the running table against this baseline tells
+/// external edits apart from the app's own writes and detects conflicting edits.
+#[derive(Debug, Clone)]
+pub struct ConfigSync {
+    baseline: Table,
+}
+
+impl ConfigSync {
+    pub fn new(table: &Table) -> Self {
+        Self {
+            baseline: table.clone(),
+        }
+    }
+
+    /// Records `table` as the state shared by the app and the file.
+    pub fn mark_synced(&mut self, table: &Table) {
+        self.baseline = table.clone();
+    }
+
+    /// Whether `local` has changes that were never written to the file.
+    pub fn is_dirty(&self, local: &Table) -> bool {
+        *local != self.baseline
+    }
+
+    /// When the file already matches `local`, e.g. after the same edit was made both
+    /// here and on disk, that becomes the new baseline.
+    pub fn classify(&mut self, local: &Table, disk: Table) -> ExternalChange {
+        if disk == self.baseline {
+            ExternalChange::Unchanged
+        } else if disk == *local {
+            self.baseline = disk;
+            ExternalChange::Unchanged
+        } else if *local == self.baseline {
+            ExternalChange::Reloadable(disk)
+        } else {
+            ExternalChange::Conflict(disk)
+        }
+    }
+}
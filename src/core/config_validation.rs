@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use super::config_migration::migrate_to_current;
+use super::table::{AppConfigFile, TableConfigCellKind};
+use super::ConfigError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DiagnosticSeverity {
+    Warning,
+    Error,
+}
+
+impl DiagnosticSeverity {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// One finding from config validation, with enough context to locate it in the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigDiagnostic {
+    pub severity: DiagnosticSeverity,
+    /// One-based line in the source file, when it can be determined.
+    pub line: Option<usize>,
+    /// JSON path of the offending value, e.g. `default_table.subjects[3]`.
+    pub field: String,
+    pub message: String,
+}
+
+impl ConfigDiagnostic {
+    fn new(
+        severity: DiagnosticSeverity,
+        line: Option<usize>,
+        field: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            severity,
+            line,
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Converts a load failure into a diagnostic so it can be reported alongside findings.
+    pub fn from_error(error: &ConfigError) -> Self {
+        let line = match error {
+            ConfigError::Parse(error) if error.line() > 0 => Some(error.line()),
+            _ => None,
+        };
+        Self::new(DiagnosticSeverity::Error, line, "", error.to_string())
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == DiagnosticSeverity::Error
+    }
+}
+
+impl fmt::Display for ConfigDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.severity.label())?;
+        if let Some(line) = self.line {
+            write!(f, ": line {line}")?;
+        }
+        if !self.field.is_empty() {
+            write!(f, ": {}", self.field)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Reads and validates a config file. I/O and parse failures are reported as diagnostics.
+pub fn validate_config_file(config_file: &Path) -> Vec<ConfigDiagnostic> {
+    match fs::read_to_string(config_file) {
+        Ok(text) => validate_config_text(&text),
+        Err(error) => vec![ConfigDiagnostic::from_error(&ConfigError::Io(error))],
+    }
+}
+
+/// Validates config JSON in any supported schema version.
+///
+/// `Table::load_config` is lenient: it drops out-of-bounds cells and keeps the last of
+/// duplicate positions. This pass reports those cases instead of hiding them.
+pub fn validate_config_text(text: &str) -> Vec<ConfigDiagnostic> {
+    let payload = serde_json::from_str(text)
+        .map_err(ConfigError::from)
+        .and_then(migrate_to_current)
+        .and_then(|value| serde_json::from_value::<AppConfigFile>(value).map_err(Into::into));
+    let payload = match payload {
+        Ok(payload) => payload,
+        Err(error) => return vec![ConfigDiagnostic::from_error(&error)],
+    };
+
+    let table = payload.default_table;
+    let subject_lines = array_element_lines(text, "subjects");
+    let line_of = |index: usize| subject_lines.get(index).copied();
+    let mut diagnostics = Vec::new();
+
+    if table.row_count == 0 || table.column_count == 0 {
+        diagnostics.push(ConfigDiagnostic::new(
+            DiagnosticSeverity::Error,
+            None,
+            "default_table",
+            format!(
+                "table must have at least one row and column, found {}x{}",
+                table.column_count, table.row_count
+            ),
+        ));
+    }
+
+    for (field, labels, count, unit) in [
+        ("row_labels", &table.row_labels, table.row_count, "rows"),
+        (
+            "column_labels",
+            &table.column_labels,
+            table.column_count,
+            "columns",
+        ),
+    ] {
+        if labels.len() > count as usize {
+            diagnostics.push(ConfigDiagnostic::new(
+                DiagnosticSeverity::Warning,
+                None,
+                format!("default_table.{field}"),
+                format!(
+                    "{} labels for {count} {unit}; the extra labels are ignored",
+                    labels.len()
+                ),
+            ));
+        }
+    }
+
+    let merged_lines = array_element_lines(text, "merged_cells");
+    let mut merged_areas: Vec<(u32, u32, u32, u32)> = Vec::new();
+    for (index, merged) in table.merged_cells.iter().enumerate() {
+        let field = format!("default_table.merged_cells[{index}]");
+        let line = merged_lines.get(index).copied();
+        let far_x = merged.x.saturating_add(merged.columns.max(1) - 1);
+        let far_y = merged.y.saturating_add(merged.rows.max(1) - 1);
+        if far_x >= table.column_count || far_y >= table.row_count {
+            diagnostics.push(ConfigDiagnostic::new(
+                DiagnosticSeverity::Error,
+                line,
+                field,
+                format!(
+                    "merged cell at ({}, {}) extends outside the {}x{} table and will be dropped",
+                    merged.x, merged.y, table.column_count, table.row_count
+                ),
+            ));
+            continue;
+        }
+        if merged_areas.iter().any(|(x, y, other_far_x, other_far_y)| {
+            merged.x <= *other_far_x && far_x >= *x && merged.y <= *other_far_y && far_y >= *y
+        }) {
+            diagnostics.push(ConfigDiagnostic::new(
+                DiagnosticSeverity::Warning,
+                line,
+                field,
+                format!(
+                    "merged cell at ({}, {}) overlaps an earlier one; they are combined",
+                    merged.x, merged.y
+                ),
+            ));
+        }
+        merged_areas.push((merged.x, merged.y, far_x, far_y));
+    }
+
+    let mut positions: HashMap<(u32, u32), usize> = HashMap::new();
+    let mut names: HashMap<String, usize> = HashMap::new();
+    for (index, subject) in table.subjects.iter().enumerate() {
+        let field = format!("default_table.subjects[{index}]");
+
+        if subject.x >= table.column_count || subject.y >= table.row_count {
+            diagnostics.push(ConfigDiagnostic::new(
+                DiagnosticSeverity::Error,
+                line_of(index),
+                field.clone(),
+                format!(
+                    "position ({}, {}) is outside the {}x{} table and will be dropped",
+                    subject.x, subject.y, table.column_count, table.row_count
+                ),
+            ));
+        }
+
+        if let Some(first) = positions.insert((subject.x, subject.y), index) {
+            diagnostics.push(ConfigDiagnostic::new(
+                DiagnosticSeverity::Error,
+                line_of(index),
+                field.clone(),
+                format!(
+                    "position ({}, {}) is already used by subjects[{first}]; this entry replaces it",
+                    subject.x, subject.y
+                ),
+            ));
+        }
+
+        if !matches!(subject.kind, TableConfigCellKind::Active) {
+            continue;
+        }
+        if subject.name.is_none() && !subject.groups.is_empty() {
+            // An empty seat that only carries group tags.
+            continue;
+        }
+        let name = subject.name.as_deref().unwrap_or_default().trim();
+        if name.is_empty() {
+            diagnostics.push(ConfigDiagnostic::new(
+                DiagnosticSeverity::Warning,
+                line_of(index),
+                format!("{field}.name"),
+                format!(
+                    "active seat at ({}, {}) has an empty name",
+                    subject.x, subject.y
+                ),
+            ));
+        } else if let Some(first) = names.get(name) {
+            diagnostics.push(ConfigDiagnostic::new(
+                DiagnosticSeverity::Warning,
+                line_of(index),
+                format!("{field}.name"),
+                format!("name \"{name}\" is also used by subjects[{first}]"),
+            ));
+        } else {
+            names.insert(name.to_owned(), index);
+        }
+    }
+
+    diagnostics
+}
+
+/// Finds the one-based line of each element in the first JSON array stored under `key`.
+///
+/// Returns an empty list when the key is missing, e.g. for migrated legacy files.
+fn array_element_lines(text: &str, key: &str) -> Vec<usize> {
+    let needle = format!("\"{key}\"");
+    let Some(key_start) = text.find(&needle) else {
+        return Vec::new();
+    };
+    let after_key = &text[key_start + needle.len()..];
+    let Some(open) = after_key.find('[') else {
+        return Vec::new();
+    };
+    let array_start = key_start + needle.len() + open;
+
+    let mut lines = Vec::new();
+    let mut line = 1 + text[..array_start].matches('\n').count();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut expecting_element = false;
+
+    for character in text[array_start..].chars() {
+        if character == '\n' {
+            line += 1;
+        }
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if character == '\\' {
+                escaped = true;
+            } else if character == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        if depth == 1 && expecting_element && !character.is_whitespace() && character != ']' {
+            lines.push(line);
+            expecting_element = false;
+        }
+        match character {
+            '"' => in_string = true,
+            '[' | '{' => {
+                depth += 1;
+                if depth == 1 {
+                    expecting_element = true;
+                }
+            }
+            ']' | '}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    break;
+                }
+            }
+            ',' if depth == 1 => expecting_element = true,
+            _ => {}
+        }
+    }
+
+    lines
+}
@@ -1,22 +1,239 @@
 use std::env;
+use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
+use super::settings::{
+    BoardChoice, DefaultLayout, Locale, SettingsError, SettingsLayer, ThemeChoice,
+};
+use super::{AppMode, ConfigBackups, SessionStore, EXPORT_TEMPLATE_EN, EXPORT_TEMPLATE_ZH};
+
+const APP_DIR_NAME: &str = "checkin";
+const CONFIG_FILE_NAME: &str = "table.conf.json";
+const SETTINGS_FILE_NAME: &str = "settings.json";
+const THEME_FILE_NAME: &str = "theme.json";
+
+/// Runtime paths and options, resolved from the command line, `CHECKIN_*` environment
+/// variables and the settings file, in that order of precedence.
+///
+/// Directories follow the XDG base directory spec (`$XDG_CONFIG_HOME`, `$XDG_DATA_HOME`,
+/// `$XDG_CACHE_HOME`), with `%APPDATA%`/`%LOCALAPPDATA%` used on Windows.
 #[derive(Debug, Clone)]
 pub struct Configuration {
     pub config_file: PathBuf,
+    pub settings_file: PathBuf,
+    /// Per-user directory for settings such as the default config file.
+    pub config_dir: PathBuf,
+    /// Per-user directory for data the app produces: sessions and backups.
+    pub data_dir: PathBuf,
+    /// Per-user directory for disposable files.
+    pub cache_dir: PathBuf,
+    /// Number of timestamped config backups to keep; `0` disables backups.
+    pub backup_count: usize,
+    pub initial_mode: AppMode,
+    pub default_layout: DefaultLayout,
+    pub locale: Locale,
+    pub theme: ThemeChoice,
+    pub board: BoardChoice,
+    /// How often unsaved layout edits are written to the config file; zero disables it.
+    pub autosave_interval: Duration,
+    /// Overrides the locale's statistics export template when set.
+    pub export_template: Option<String>,
+    /// Port of the local REST API on 127.0.0.1; `None` keeps it off.
+    pub api_port: Option<u16>,
+    /// Port the self check-in page listens on, on all interfaces, once it is opened.
+    pub self_checkin_port: u16,
+    /// Student ID to name list used by card scans.
+    pub roster_file: Option<PathBuf>,
+    /// Check-ins later than this after the session start are stored as late.
+    pub late_after: Option<Duration>,
 }
 
 impl Configuration {
+    pub const DEFAULT_BACKUP_COUNT: usize = 5;
+    pub const DEFAULT_SELF_CHECKIN_PORT: u16 = 8788;
+
     pub fn new(config_file: PathBuf) -> Self {
-        Self { config_file }
+        let config_dir = Self::default_config_dir();
+        Self {
+            config_file,
+            settings_file: config_dir.join(SETTINGS_FILE_NAME),
+            config_dir,
+            data_dir: Self::default_data_dir(),
+            cache_dir: Self::default_cache_dir(),
+            backup_count: Self::DEFAULT_BACKUP_COUNT,
+            initial_mode: AppMode::default(),
+            default_layout: DefaultLayout::default(),
+            locale: Locale::default(),
+            theme: ThemeChoice::default(),
+            board: BoardChoice::default(),
+            autosave_interval: Duration::ZERO,
+            export_template: None,
+            api_port: None,
+            self_checkin_port: Self::DEFAULT_SELF_CHECKIN_PORT,
+            roster_file: None,
+            late_after: None,
+        }
+    }
+
+    /// Merges the command-line and environment layers over the settings file.
+    ///
+    /// The settings file location itself comes from the first two layers only.
+    pub fn resolve(cli: SettingsLayer, env: SettingsLayer) -> Result<Self, SettingsError> {
+        let layered = cli.or(env);
+        let defaults = Self::new(Self::default_config_file());
+        let settings_file = layered
+            .settings_file
+            .clone()
+            .unwrap_or_else(|| defaults.settings_file.clone());
+        let file = SettingsLayer::from_file(&settings_file)?;
+        let resolved = layered.or(file);
+
+        Ok(Self {
+            config_file: resolved.config_file.unwrap_or(defaults.config_file),
+            settings_file,
+            backup_count: resolved.backup_count.unwrap_or(defaults.backup_count),
+            initial_mode: resolved.initial_mode.unwrap_or(defaults.initial_mode),
+            default_layout: resolved.default_layout.unwrap_or(defaults.default_layout),
+            locale: resolved.locale.unwrap_or(defaults.locale),
+            theme: resolved.theme.unwrap_or(defaults.theme),
+            board: resolved.board.unwrap_or(defaults.board),
+            autosave_interval: resolved
+                .autosave_interval
+                .unwrap_or(defaults.autosave_interval),
+            export_template: resolved.export_template,
+            api_port: resolved.api_port.filter(|port| *port != 0),
+            self_checkin_port: resolved
+                .self_checkin_port
+                .unwrap_or(defaults.self_checkin_port),
+            roster_file: resolved.roster_file,
+            late_after: resolved.late_after,
+            ..defaults
+        })
+    }
+
+    /// Template for the copied statistics text.
+    pub fn export_template(&self) -> &str {
+        match (&self.export_template, self.locale) {
+            (Some(template), _) => template,
+            (None, Locale::Zh) => EXPORT_TEMPLATE_ZH,
+            (None, Locale::En) => EXPORT_TEMPLATE_EN,
+        }
+    }
+
+    /// Directory for saved check-in sessions.
+    pub fn sessions_dir(&self) -> PathBuf {
+        self.data_dir.join("sessions")
+    }
+
+    pub fn session_store(&self) -> SessionStore {
+        SessionStore::new(self.sessions_dir())
+    }
+
+    /// Colors for `--theme custom`, see [`Theme`](super::Theme).
+    pub fn theme_file(&self) -> PathBuf {
+        self.config_dir.join(THEME_FILE_NAME)
+    }
+
+    /// Remembered board zoom level, see [`Zoom`](super::Zoom).
+    pub fn zoom_file(&self) -> PathBuf {
+        self.cache_dir.join("zoom")
+    }
+
+    /// Token of the running REST API, for local scripts to read.
+    pub fn api_token_file(&self) -> PathBuf {
+        self.cache_dir.join("api-token")
+    }
+
+    /// Directory for timestamped config backups.
+    pub fn backup_dir(&self) -> PathBuf {
+        self.data_dir.join("backups")
+    }
+
+    pub fn backups(&self) -> ConfigBackups {
+        ConfigBackups::new(self.backup_dir(), self.backup_count)
     }
 
     pub fn default_config_file() -> PathBuf {
+        Self::default_config_dir().join(CONFIG_FILE_NAME)
+    }
+
+    pub fn default_config_dir() -> PathBuf {
+        Self::user_dir("XDG_CONFIG_HOME", "APPDATA", ".config")
+    }
+
+    pub fn default_data_dir() -> PathBuf {
+        Self::user_dir("XDG_DATA_HOME", "APPDATA", ".local/share")
+    }
+
+    pub fn default_cache_dir() -> PathBuf {
+        Self::user_dir("XDG_CACHE_HOME", "LOCALAPPDATA", ".cache")
+    }
+
+    /// Where older builds kept the config: next to the executable.
+    pub fn legacy_config_file() -> PathBuf {
+        Self::executable_dir().join(CONFIG_FILE_NAME)
+    }
+
+    /// Copies a config left next to the executable by older builds into the config dir.
+    ///
+    /// Only applies when the default config file is in use and doesn't exist yet, so it
+    /// runs at most once. The legacy file is left in place since the install directory
+    /// may be read-only. Returns the legacy path when a copy was made.
+    pub fn migrate_legacy_config_file(&self) -> io::Result<Option<PathBuf>> {
+        if self.config_file != Self::default_config_file() || self.config_file.exists() {
+            return Ok(None);
+        }
+
+        let legacy = Self::legacy_config_file();
+        if legacy == self.config_file || !legacy.is_file() {
+            return Ok(None);
+        }
+
+        if let Some(parent) = self.config_file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(&legacy, &self.config_file)?;
+        Ok(Some(legacy))
+    }
+
+    /// Creates the config, data and cache directories if they are missing.
+    pub fn ensure_dirs(&self) -> io::Result<()> {
+        for dir in [&self.config_dir, &self.data_dir, &self.cache_dir] {
+            fs::create_dir_all(dir)?;
+        }
+        Ok(())
+    }
+
+    fn user_dir(xdg_var: &str, windows_var: &str, home_relative: &str) -> PathBuf {
+        let absolute_var = |name: &str| {
+            env::var_os(name)
+                .map(PathBuf::from)
+                .filter(|path| path.is_absolute())
+        };
+        let base = absolute_var(xdg_var)
+            .or_else(|| {
+                if cfg!(windows) {
+                    absolute_var(windows_var)
+                } else {
+                    None
+                }
+            })
+            .or_else(|| {
+                absolute_var("HOME")
+                    .or_else(|| absolute_var("USERPROFILE"))
+                    .map(|home| home.join(home_relative))
+            })
+            .unwrap_or_else(Self::executable_dir);
+        base.join(APP_DIR_NAME)
+    }
+
+    fn executable_dir() -> PathBuf {
         env::current_exe()
             .ok()
             .and_then(|path| path.parent().map(Path::to_path_buf))
             .or_else(|| env::current_dir().ok())
             .unwrap_or_else(|| PathBuf::from("."))
-            .join("table.conf.json")
     }
 }
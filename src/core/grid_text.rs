@@ -0,0 +1,129 @@
+use super::{Position, Subject, Table};
+
+/// Cell text marking a blocked seat. Any text after the marker becomes the block name.
+pub const BLOCK_MARKER: &str = "#";
+/// Cell text marking a transparent placeholder.
+pub const TRANSPARENT_MARKER: &str = "~";
+
+/// Rectangular grid of subjects parsed from spreadsheet-style text.
+///
+/// `None` entries are empty spreadsheet cells and clear the target seat on paste.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GridText {
+    rows: Vec<Vec<Option<Subject>>>,
+}
+
+impl GridText {
+    /// Parses tab-separated text as copied from a spreadsheet.
+    ///
+    /// Rows are split on line breaks (`\n` or `\r\n`) and a trailing empty line is ignored.
+    pub fn parse(text: &str) -> Self {
+        let mut lines: Vec<&str> = text.split('\n').collect();
+        if lines
+            .last()
+            .is_some_and(|line| line.trim_end_matches('\r').is_empty())
+        {
+            lines.pop();
+        }
+
+        let rows = lines
+            .into_iter()
+            .map(|line| {
+                line.trim_end_matches('\r')
+                    .split('\t')
+                    .map(Self::parse_cell)
+                    .collect()
+            })
+            .collect();
+
+        Self { rows }
+    }
+
+    /// Captures the rectangle spanned by `start` and `end` (inclusive) from a table.
+    pub fn from_table(table: &Table, start: Position, end: Position) -> Self {
+        let (min_x, max_x) = (start.x.min(end.x), start.x.max(end.x));
+        let (min_y, max_y) = (start.y.min(end.y), start.y.max(end.y));
+        let max_x = max_x.min(table.column_count().saturating_sub(1));
+        let max_y = max_y.min(table.row_count().saturating_sub(1));
+
+        let rows = (min_y..=max_y)
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| table.subject_at_owned(Position { x, y }))
+                    .collect()
+            })
+            .collect();
+
+        Self { rows }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.iter().all(Vec::is_empty)
+    }
+
+    pub fn row_count(&self) -> u32 {
+        self.rows.len() as u32
+    }
+
+    /// Width of the widest row; ragged rows are treated as padded with empty cells.
+    pub fn column_count(&self) -> u32 {
+        self.rows.iter().map(Vec::len).max().unwrap_or(0) as u32
+    }
+
+    /// Iterates non-padded cells with offsets relative to the grid origin.
+    pub fn iter_cells(&self) -> impl Iterator<Item = (Position, Option<&Subject>)> + '_ {
+        self.rows.iter().enumerate().flat_map(|(y, row)| {
+            row.iter().enumerate().map(move |(x, subject)| {
+                (
+                    Position {
+                        x: x as u32,
+                        y: y as u32,
+                    },
+                    subject.as_ref(),
+                )
+            })
+        })
+    }
+
+    /// Serializes the grid as tab-separated text using the same markers `parse` accepts.
+    pub fn to_tsv(&self) -> String {
+        let mut text = String::new();
+        for row in &self.rows {
+            let cells: Vec<String> = row
+                .iter()
+                .map(|cell| Self::format_cell(cell.as_ref()))
+                .collect();
+            text.push_str(&cells.join("\t"));
+            text.push('\n');
+        }
+        text
+    }
+
+    fn parse_cell(raw: &str) -> Option<Subject> {
+        let text = raw.trim();
+        if text.is_empty() {
+            return None;
+        }
+        if text == TRANSPARENT_MARKER {
+            return Some(Subject::Transparent);
+        }
+        if let Some(name) = text.strip_prefix(BLOCK_MARKER) {
+            return Some(Subject::Block(name.trim().to_owned()));
+        }
+        Some(Subject::Some(text.to_owned()))
+    }
+
+    fn format_cell(subject: Option<&Subject>) -> String {
+        match subject {
+            Some(Subject::Transparent) => String::from(TRANSPARENT_MARKER),
+            Some(Subject::Block(name)) => format!("{BLOCK_MARKER}{}", Self::sanitize(name)),
+            Some(Subject::Some(name)) => Self::sanitize(name),
+            None => String::new(),
+        }
+    }
+
+    /// Tabs and line breaks would split the cell, so they are folded into spaces.
+    fn sanitize(name: &str) -> String {
+        name.replace(['\t', '\r', '\n'], " ")
+    }
+}
@@ -1,11 +1,60 @@
 mod attendance;
+mod attendance_history;
+mod config_error;
+mod config_migration;
+mod config_store;
+mod config_sync;
+mod config_validation;
 mod configuration;
+mod grid_text;
 mod mode;
 mod persistence;
+mod random_picker;
+mod rest_api;
+mod roll_call;
+mod roster;
+mod scan_input;
+mod self_checkin;
+mod session;
+mod settings;
 mod table;
+mod theme;
+mod zoom;
 
-pub use attendance::{AttendanceBook, AttendanceStatistics, AttendanceStatus};
+pub use attendance::{
+    AttendanceBook, AttendanceStatistics, AttendanceStatus, EXPORT_TEMPLATE_EN, EXPORT_TEMPLATE_ZH,
+};
+pub use attendance_history::{
+    is_slipping, person_attendance, recent_completion, PersonAttendance, PersonAttendanceColumn,
+    SessionCompletion,
+};
+pub use config_error::ConfigError;
+pub use config_migration::CONFIG_VERSION;
+pub use config_store::{write_file_atomic, write_private_file_atomic, ConfigBackups};
+pub use config_sync::{ConfigSync, ExternalChange};
+pub use config_validation::{
+    validate_config_file, validate_config_text, ConfigDiagnostic, DiagnosticSeverity,
+};
 pub use configuration::Configuration;
+pub use grid_text::{GridText, BLOCK_MARKER, TRANSPARENT_MARKER};
 pub use mode::AppMode;
 pub use persistence::{AttendanceSave, PositionSave, SaveData, TableSave};
-pub use table::{CellKind, Position, Subject, Table};
+pub use random_picker::RandomPicker;
+pub use rest_api::{ApiAccess, ApiRequest, ApiResponse, API_TOKEN_HEADER};
+pub use roll_call::{RollCall, RollCallAnswer, RollCallOrder};
+pub use roster::Roster;
+pub use scan_input::{
+    check_in_scanned, ScanBuffer, ScanOutcome, MAX_SCAN_KEY_GAP, MIN_SCAN_LENGTH,
+};
+pub use self_checkin::{
+    SelfCheckIn, SelfCheckInError, SelfCheckInOutcome, PIN_LENGTH, PIN_ROTATION_INTERVAL,
+    TOKEN_LENGTH, TOKEN_ROTATION_INTERVAL,
+};
+pub use session::{SessionEntry, SessionRecord, SessionStatus, SessionStore};
+pub use settings::{
+    BoardChoice, CliCommand, DefaultLayout, Locale, SettingsError, SettingsLayer, ThemeChoice,
+    USAGE,
+};
+pub use table::{CellKind, CellSpan, Position, Subject, Table};
+pub use theme::{Rgba, SeatColors, Theme};
+pub use zoom::{Zoom, MAX_ZOOM, MIN_ZOOM, ZOOM_STEP};
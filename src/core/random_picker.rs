@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use rand::distributions::WeightedIndex;
+use rand::prelude::Distribution;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use super::{AttendanceBook, AttendanceStatus, Position, Table};
+
+/// Picks a random `Checked` person, remembering who was picked this session.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RandomPicker {
+    pick_counts: HashMap<Position, u32>,
+    /// Skip people already picked until everyone present has had a turn.
+    pub avoid_repeats: bool,
+    /// Favor people picked less often so far.
+    pub weight_by_history: bool,
+}
+
+impl RandomPicker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn pick_count(&self, position: Position) -> u32 {
+        self.pick_counts.get(&position).copied().unwrap_or(0)
+    }
+
+    /// Forgets who has been picked.
+    pub fn reset(&mut self) {
+        self.pick_counts.clear();
+    }
+
+    /// `Checked` seats eligible for the next pick.
+    ///
+    /// With `avoid_repeats`, seats picked fewer times than the rest come first; once
+    /// everyone has had a turn, a new round starts over all of them.
+    pub fn candidates(&self, table: &Table, attendance: &AttendanceBook) -> Vec<Position> {
+        let present: Vec<Position> = table
+            .iter_positions()
+            .filter(|position| attendance.status_at(*position) == Some(AttendanceStatus::Checked))
+            .collect();
+        if !self.avoid_repeats {
+            return present;
+        }
+
+        let fewest = present
+            .iter()
+            .map(|position| self.pick_count(*position))
+            .min()
+            .unwrap_or(0);
+        present
+            .into_iter()
+            .filter(|position| self.pick_count(*position) == fewest)
+            .collect()
+    }
+
+    /// Picks and records one candidate; `None` when nobody is `Checked`.
+    pub fn pick<R: Rng + ?Sized>(
+        &mut self,
+        table: &Table,
+        attendance: &AttendanceBook,
+        rng: &mut R,
+    ) -> Option<Position> {
+        let candidates = self.candidates(table, attendance);
+        let picked = if self.weight_by_history {
+            let weights = candidates
+                .iter()
+                .map(|position| 1.0 / f64::from(self.pick_count(*position) + 1));
+            let index = WeightedIndex::new(weights).ok()?.sample(rng);
+            candidates[index]
+        } else {
+            *candidates.choose(rng)?
+        };
+
+        *self.pick_counts.entry(picked).or_default() += 1;
+        Some(picked)
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde_json::{json, Value};
+
+use super::{AttendanceBook, AttendanceStatistics, AttendanceStatus, CellKind, Position, Table};
+
+/// Characters in the per-run API token.
+pub const API_TOKEN_LENGTH: usize = 32;
+/// Request header carrying the API token.
+pub const API_TOKEN_HEADER: &str = "X-Checkin-Token";
+
+/// Who may use the local REST API.
+///
+/// Requests must carry the token of this run in the [`API_TOKEN_HEADER`] header and be
+/// addressed to a loopback host. Web pages in the teacher's browser can't send that
+/// header cross-site without a CORS preflight, which this API never grants, and DNS
+/// rebinding fails the host check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiAccess {
+    token: String,
+}
+
+impl Default for ApiAccess {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ApiAccess {
+    /// Issues a fresh random token.
+    pub fn new() -> Self {
+        let token = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(API_TOKEN_LENGTH)
+            .map(char::from)
+            .collect();
+        Self { token }
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Checks a request before it is routed. `headers` are keyed by lowercased name.
+    ///
+    /// `POST` bodies must be sent as `application/json`.
+    pub fn check(
+        &self,
+        method: &str,
+        headers: &HashMap<String, String>,
+    ) -> Result<(), ApiResponse> {
+        let header = |name: &str| headers.get(name).map(|value| value.trim());
+
+        if !header("host").is_some_and(is_loopback_host) {
+            return Err(ApiResponse::error(
+                403,
+                "requests must be addressed to localhost",
+            ));
+        }
+        let cross_origin = header("origin").is_some_and(|origin| {
+            let host = origin
+                .strip_prefix("http://")
+                .or_else(|| origin.strip_prefix("https://"));
+            !host.is_some_and(is_loopback_host)
+        });
+        if cross_origin {
+            return Err(ApiResponse::error(
+                403,
+                "cross-origin requests are not allowed",
+            ));
+        }
+        let token = header(&API_TOKEN_HEADER.to_ascii_lowercase());
+        if !token.is_some_and(|token| tokens_match(token, &self.token)) {
+            return Err(ApiResponse::error(
+                401,
+                format!("missing or wrong {API_TOKEN_HEADER} header"),
+            ));
+        }
+        let is_json = header("content-type").is_some_and(|content_type| {
+            content_type.split(';').next().is_some_and(|media_type| {
+                media_type.trim().eq_ignore_ascii_case("application/json")
+            })
+        });
+        if method == "POST" && !is_json {
+            return Err(ApiResponse::error(
+                415,
+                "request body must be application/json",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// `localhost`, `127.0.0.1` or `[::1]`, with or without a port.
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(bracketed) => bracketed.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    name.eq_ignore_ascii_case("localhost") || name == "127.0.0.1" || name == "::1"
+}
+
+/// Compares in time independent of where the first difference is.
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+/// Operation requested through the local REST API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiRequest {
+    GetTable,
+    GetStatistics,
+    GetAttendance,
+    SetStatus {
+        position: Position,
+        status: AttendanceStatus,
+    },
+}
+
+/// HTTP status code plus JSON body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiResponse {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl ApiResponse {
+    pub fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    pub fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+impl ApiRequest {
+    /// Maps an HTTP method, path and body to a request.
+    ///
+    /// `POST /attendance/{x}/{y}` takes a JSON body such as `{"status": "checked"}`.
+    /// Requests should pass [`ApiAccess::check`] first.
+    pub fn route(method: &str, path: &str, body: &str) -> Result<Self, ApiResponse> {
+        let segments: Vec<&str> = path
+            .trim_matches('/')
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+
+        match (method, segments.as_slice()) {
+            ("GET", ["table"]) => Ok(Self::GetTable),
+            ("GET", ["statistics"]) => Ok(Self::GetStatistics),
+            ("GET", ["attendance"]) => Ok(Self::GetAttendance),
+            ("POST", ["attendance", x, y]) => {
+                let (Ok(x), Ok(y)) = (x.parse(), y.parse()) else {
+                    return Err(ApiResponse::error(400, "seat coordinates must be numbers"));
+                };
+                let status = Self::parse_status(body).ok_or_else(|| {
+                    ApiResponse::error(400, "status must be checked, unchecked or marked")
+                })?;
+                Ok(Self::SetStatus {
+                    position: Position { x, y },
+                    status,
+                })
+            }
+            (_, ["table"] | ["statistics"] | ["attendance"] | ["attendance", _, _]) => {
+                Err(ApiResponse::error(405, "method not allowed"))
+            }
+            _ => Err(ApiResponse::error(404, "not found")),
+        }
+    }
+
+    /// Runs the request against the current state.
+    ///
+    /// Status changes go through [`AttendanceBook::update_status`]; the returned flag is
+    /// `true` when attendance actually changed.
+    pub fn execute(&self, table: &Table, attendance: &mut AttendanceBook) -> (ApiResponse, bool) {
+        match *self {
+            Self::GetTable => (ApiResponse::ok(table_json(table)), false),
+            Self::GetStatistics => (
+                ApiResponse::ok(statistics_json(attendance.statistics(table))),
+                false,
+            ),
+            Self::GetAttendance => (ApiResponse::ok(attendance_json(table, attendance)), false),
+            Self::SetStatus { position, status } => {
+                if !table.contains(position) {
+                    return (ApiResponse::error(404, "seat is outside the table"), false);
+                }
+                if table.is_inert(position) {
+                    return (
+                        ApiResponse::error(409, "seat is blocked or transparent"),
+                        false,
+                    );
+                }
+                let changed = attendance.update_status(table, position, status);
+                let body = json!({
+                    "x": position.x,
+                    "y": position.y,
+                    "seat": table.seat_label(position),
+                    "status": status.api_name(),
+                    "changed": changed,
+                });
+                (ApiResponse::ok(body), changed)
+            }
+        }
+    }
+
+    fn parse_status(body: &str) -> Option<AttendanceStatus> {
+        serde_json::from_str::<Value>(body)
+            .ok()?
+            .get("status")
+            .and_then(Value::as_str)
+            .and_then(AttendanceStatus::from_api_name)
+    }
+}
+
+fn table_json(table: &Table) -> Value {
+    let cells: Vec<Value> = table
+        .iter_positions()
+        .map(|position| {
+            let kind = match table.cell_kind(position) {
+                Some(CellKind::Blocked) => "blocked",
+                Some(CellKind::Transparent) => "transparent",
+                _ => "active",
+            };
+            json!({
+                "x": position.x,
+                "y": position.y,
+                "seat": table.seat_label(position),
+                "kind": kind,
+                "name": table.subject_at(position).and_then(|subject| subject.name()),
+            })
+        })
+        .collect();
+
+    json!({
+        "row_count": table.row_count(),
+        "column_count": table.column_count(),
+        "row_labels": (0..table.row_count()).map(|y| table.row_label(y)).collect::<Vec<_>>(),
+        "column_labels": (0..table.column_count())
+            .map(|x| table.column_label(x))
+            .collect::<Vec<_>>(),
+        "cells": cells,
+    })
+}
+
+fn statistics_json(statistics: AttendanceStatistics) -> Value {
+    json!({
+        "checked": statistics.checked,
+        "unchecked": statistics.unchecked,
+        "marked": statistics.marked,
+        "active_total": statistics.active_total,
+        "blocked_total": statistics.blocked_total,
+        "completed_count": statistics.completed_count(),
+        "completed_percent": statistics.completed_ratio_percent(),
+    })
+}
+
+fn attendance_json(table: &Table, attendance: &AttendanceBook) -> Value {
+    let seats: Vec<Value> = table
+        .iter_positions()
+        .filter_map(|position| {
+            let status = attendance.status_at(position)?;
+            Some(json!({
+                "x": position.x,
+                "y": position.y,
+                "seat": table.seat_label(position),
+                "name": table.subject_at(position).and_then(|subject| subject.name()),
+                "status": status.api_name(),
+            }))
+        })
+        .collect();
+    Value::Array(seats)
+}
@@ -0,0 +1,144 @@
+use super::{AttendanceBook, AttendanceStatus, Position, Subject, Table};
+
+/// Order in which a roll call visits seats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RollCallOrder {
+    /// Row-major, as in [`Table::iter_positions`].
+    #[default]
+    Seating,
+    Alphabetical,
+}
+
+impl RollCallOrder {
+    pub const ALL: [Self; 2] = [Self::Seating, Self::Alphabetical];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Seating => "seating order",
+            Self::Alphabetical => "alphabetical",
+        }
+    }
+}
+
+/// Answer given when a name is called out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollCallAnswer {
+    Present,
+    Absent,
+    Leave,
+}
+
+impl RollCallAnswer {
+    pub const ALL: [Self; 3] = [Self::Present, Self::Absent, Self::Leave];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Present => "present",
+            Self::Absent => "absent",
+            Self::Leave => "leave",
+        }
+    }
+
+    /// Single-key shortcut for the answer.
+    pub const fn key(self) -> char {
+        match self {
+            Self::Present => 'p',
+            Self::Absent => 'a',
+            Self::Leave => 'l',
+        }
+    }
+
+    pub fn from_key(key: char) -> Option<Self> {
+        let key = key.to_ascii_lowercase();
+        Self::ALL.into_iter().find(|answer| answer.key() == key)
+    }
+
+    pub const fn status(self) -> AttendanceStatus {
+        match self {
+            Self::Present => AttendanceStatus::Checked,
+            Self::Absent => AttendanceStatus::Unchecked,
+            Self::Leave => AttendanceStatus::Marked,
+        }
+    }
+}
+
+/// Steps through named active seats one at a time.
+///
+/// Unnamed seats are skipped since there is nobody to call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollCall {
+    seats: Vec<(Position, String)>,
+    index: usize,
+}
+
+impl RollCall {
+    pub fn new(table: &Table, order: RollCallOrder) -> Self {
+        let mut seats: Vec<(Position, String)> = table
+            .iter_positions()
+            .filter(|position| !table.is_inert(*position))
+            .filter_map(|position| {
+                let name = table.subject_at(position).and_then(Subject::name)?;
+                (!name.trim().is_empty()).then(|| (position, name.clone()))
+            })
+            .collect();
+        if order == RollCallOrder::Alphabetical {
+            // Stable, so namesakes keep their seating order.
+            seats.sort_by_key(|(_, name)| name.to_lowercase());
+        }
+
+        Self { seats, index: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.seats.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seats.is_empty()
+    }
+
+    /// Zero-based index of the current seat; equals `len()` once finished.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.index >= self.seats.len()
+    }
+
+    pub fn current(&self) -> Option<(Position, &str)> {
+        self.seats
+            .get(self.index)
+            .map(|(position, name)| (*position, name.as_str()))
+    }
+
+    /// Records the answer for the current seat and moves on.
+    ///
+    /// Returns whether attendance changed.
+    pub fn answer(
+        &mut self,
+        table: &Table,
+        attendance: &mut AttendanceBook,
+        answer: RollCallAnswer,
+    ) -> bool {
+        let Some((position, _)) = self.current() else {
+            return false;
+        };
+        let changed = attendance.update_status(table, position, answer.status());
+        self.advance();
+        changed
+    }
+
+    pub fn advance(&mut self) {
+        self.index = (self.index + 1).min(self.seats.len());
+    }
+
+    /// Steps back to the previous seat so its answer can be corrected.
+    pub fn back(&mut self) -> bool {
+        if self.index == 0 {
+            return false;
+        }
+        self.index -= 1;
+        true
+    }
+}
@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Maps student IDs, as printed on campus card barcodes, to seat names.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Roster {
+    names_by_id: HashMap<String, String>,
+}
+
+impl Roster {
+    /// Parses one `id<TAB>name` or `id,name` entry per line.
+    ///
+    /// Blank lines and lines starting with `#` are skipped.
+    pub fn parse(text: &str) -> io::Result<Self> {
+        let mut names_by_id = HashMap::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let entry = line.split_once('\t').or_else(|| line.split_once(','));
+            let Some((id, name)) = entry.map(|(id, name)| (id.trim(), name.trim())) else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: expected <id>,<name>", index + 1),
+                ));
+            };
+            if id.is_empty() || name.is_empty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: empty id or name", index + 1),
+                ));
+            }
+            names_by_id.insert(id.to_owned(), name.to_owned());
+        }
+        Ok(Self { names_by_id })
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names_by_id.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names_by_id.is_empty()
+    }
+
+    pub fn name_for(&self, id: &str) -> Option<&str> {
+        self.names_by_id.get(id.trim()).map(String::as_str)
+    }
+}
@@ -0,0 +1,109 @@
+use std::time::{Duration, Instant};
+
+use super::{AttendanceBook, AttendanceStatus, Position, Roster, Subject, Table};
+
+/// Longest pause between keystrokes that still counts as one scanner burst.
+pub const MAX_SCAN_KEY_GAP: Duration = Duration::from_millis(60);
+/// Shortest code accepted as a scan.
+pub const MIN_SCAN_LENGTH: usize = 3;
+
+/// Collects keystrokes from a keyboard-emulating barcode scanner.
+///
+/// Scanners type a whole code within a few milliseconds and finish with Enter. Any
+/// pause longer than [`MAX_SCAN_KEY_GAP`] starts a new burst, so slow manual typing
+/// never reaches [`ScanBuffer::finish`] as a code.
+#[derive(Debug, Clone, Default)]
+pub struct ScanBuffer {
+    code: String,
+    last_key_at: Option<Instant>,
+}
+
+impl ScanBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, character: char, at: Instant) {
+        if self.is_stale(at) {
+            self.code.clear();
+        }
+        self.code.push(character);
+        self.last_key_at = Some(at);
+    }
+
+    /// Called on Enter. Returns the scanned code if the burst qualifies.
+    pub fn finish(&mut self, at: Instant) -> Option<String> {
+        let stale = self.is_stale(at);
+        let code = std::mem::take(&mut self.code);
+        self.last_key_at = None;
+        (!stale && code.trim().chars().count() >= MIN_SCAN_LENGTH).then(|| code.trim().to_owned())
+    }
+
+    fn is_stale(&self, at: Instant) -> bool {
+        self.last_key_at
+            .is_some_and(|last| at.saturating_duration_since(last) > MAX_SCAN_KEY_GAP)
+    }
+}
+
+/// What happened to a scanned code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanOutcome {
+    Checked {
+        position: Position,
+        name: String,
+    },
+    /// The seat was already `Checked`; scanning the same card twice changes nothing.
+    Duplicate {
+        position: Position,
+        name: String,
+    },
+    /// Neither the roster nor any seat name matches the code.
+    UnknownId(String),
+    /// The matching name is on more than one active seat.
+    AmbiguousName(String),
+}
+
+impl ScanOutcome {
+    /// Unknown and duplicate scans deserve the teacher's attention.
+    pub fn needs_attention(&self) -> bool {
+        !matches!(self, Self::Checked { .. })
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            Self::Checked { name, .. } => format!("checked in: {name}"),
+            Self::Duplicate { name, .. } => format!("already checked in: {name}"),
+            Self::UnknownId(code) => format!("unknown ID: {code}"),
+            Self::AmbiguousName(name) => format!("more than one seat is named {name}"),
+        }
+    }
+}
+
+/// Marks the seat for a scanned code as `Checked`.
+///
+/// The code is looked up in the roster first; codes not in the roster are matched
+/// against seat names directly, for cards that encode the name itself.
+pub fn check_in_scanned(
+    table: &Table,
+    attendance: &mut AttendanceBook,
+    roster: &Roster,
+    code: &str,
+) -> ScanOutcome {
+    let name = roster.name_for(code).unwrap_or(code);
+    let position = match table.active_positions_named(name).as_slice() {
+        [] => return ScanOutcome::UnknownId(code.to_owned()),
+        [position] => *position,
+        _ => return ScanOutcome::AmbiguousName(name.to_owned()),
+    };
+    let name = table
+        .subject_at(position)
+        .and_then(Subject::name)
+        .cloned()
+        .unwrap_or_default();
+
+    if attendance.update_status(table, position, AttendanceStatus::Checked) {
+        ScanOutcome::Checked { position, name }
+    } else {
+        ScanOutcome::Duplicate { position, name }
+    }
+}
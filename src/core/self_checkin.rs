@@ -0,0 +1,484 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+
+use super::{AttendanceBook, AttendanceStatus, Position, Roster, Subject, Table};
+
+/// Digits in a self check-in PIN.
+pub const PIN_LENGTH: usize = 6;
+/// Wrong PINs or tokens a device may send before it is locked out.
+pub const MAX_FAILED_ATTEMPTS: u32 = 5;
+/// How long a locked-out device waits, counted from its last wrong attempt.
+pub const LOCKOUT_DURATION: Duration = Duration::from_secs(5 * 60);
+/// How often the PIN shown in the window changes.
+pub const PIN_ROTATION_INTERVAL: Duration = Duration::from_secs(60);
+/// Characters in the token carried by the check-in QR code.
+pub const TOKEN_LENGTH: usize = 12;
+/// How often the QR code token changes, so a shared screenshot soon stops working.
+pub const TOKEN_ROTATION_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Gate for students checking themselves in from their own devices.
+///
+/// Submissions must carry the PIN currently shown in the window, or the token from the
+/// QR code. The PIN and token they replaced are still accepted so a student typing
+/// during a rotation isn't turned away. A device that sends [`MAX_FAILED_ATTEMPTS`]
+/// wrong ones in a row is locked out for [`LOCKOUT_DURATION`], so PINs can't be guessed,
+/// and each device checks in a single student per session, so one phone can't mark the
+/// whole class present.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelfCheckIn {
+    open: bool,
+    pin: String,
+    previous_pin: Option<String>,
+    token: String,
+    previous_token: Option<String>,
+    /// Student IDs accepted in place of names.
+    roster: Roster,
+    failures: HashMap<IpAddr, FailedAttempts>,
+    /// Seat each device checked in since the session opened.
+    checked_in_by: HashMap<IpAddr, Position>,
+}
+
+/// Wrong credentials from one device since its last success.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FailedAttempts {
+    count: u32,
+    last: Instant,
+}
+
+/// A successful self check-in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelfCheckInOutcome {
+    pub position: Position,
+    pub name: String,
+    /// The seat was already `Checked`, so nothing changed.
+    pub already_checked: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelfCheckInError {
+    Closed,
+    WrongPin,
+    /// Too many wrong PINs from this device; it has to wait.
+    TooManyAttempts,
+    /// This device already checked in another student this session.
+    DeviceAlreadyUsed,
+    EmptyName,
+    UnknownName,
+    /// More than one active seat carries the submitted name.
+    AmbiguousName,
+    /// The app couldn't take the submission right now.
+    Busy,
+}
+
+impl fmt::Display for SelfCheckInError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Closed => write!(f, "self check-in is closed"),
+            Self::WrongPin => write!(f, "the PIN or QR code is wrong or has expired"),
+            Self::TooManyAttempts => write!(f, "too many wrong PINs, try again in a few minutes"),
+            Self::DeviceAlreadyUsed => write!(
+                f,
+                "this device already checked someone in, use your own or ask your teacher"
+            ),
+            Self::EmptyName => write!(f, "enter your name or ID"),
+            Self::UnknownName => write!(f, "no seat matches that name or ID"),
+            Self::AmbiguousName => write!(f, "more than one seat has that name, ask your teacher"),
+            Self::Busy => write!(f, "the app is busy, try again"),
+        }
+    }
+}
+
+impl std::error::Error for SelfCheckInError {}
+
+impl Default for SelfCheckIn {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SelfCheckIn {
+    /// Starts closed, with a fresh PIN.
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            pin: Self::generate_pin(),
+            previous_pin: None,
+            token: Self::generate_token(),
+            previous_token: None,
+            roster: Roster::default(),
+            failures: HashMap::new(),
+            checked_in_by: HashMap::new(),
+        }
+    }
+
+    /// Lets students check in with an ID from `roster` instead of their name.
+    pub fn set_roster(&mut self, roster: Roster) {
+        self.roster = roster;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Opening issues a fresh PIN and token, forgets the previous ones and lets every
+    /// device check in again.
+    pub fn set_open(&mut self, open: bool) {
+        if open && !self.open {
+            self.pin = Self::generate_pin();
+            self.previous_pin = None;
+            self.token = Self::generate_token();
+            self.previous_token = None;
+            self.checked_in_by.clear();
+        }
+        self.open = open;
+    }
+
+    pub fn pin(&self) -> &str {
+        &self.pin
+    }
+
+    pub fn rotate_pin(&mut self) {
+        let mut pin = Self::generate_pin();
+        while pin == self.pin {
+            pin = Self::generate_pin();
+        }
+        self.previous_pin = Some(std::mem::replace(&mut self.pin, pin));
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn rotate_token(&mut self) {
+        let token = Self::generate_token();
+        self.previous_token = Some(std::mem::replace(&mut self.token, token));
+    }
+
+    /// Check-in page URL carrying the current token, for the QR code.
+    pub fn link(&self, base_url: &str) -> String {
+        format!("{base_url}?token={}", self.token)
+    }
+
+    /// Marks the seat of `identity`, a roster ID or a name, as `Checked` when
+    /// `credential` is a valid PIN or token. `client` is the submitting device.
+    ///
+    /// Names are compared case-insensitively, ignoring surrounding whitespace.
+    pub fn check_in(
+        &mut self,
+        table: &Table,
+        attendance: &mut AttendanceBook,
+        client: IpAddr,
+        identity: &str,
+        credential: &str,
+    ) -> Result<SelfCheckInOutcome, SelfCheckInError> {
+        if !self.open {
+            return Err(SelfCheckInError::Closed);
+        }
+        let now = Instant::now();
+        self.failures
+            .retain(|_, failures| now.duration_since(failures.last) < LOCKOUT_DURATION);
+        if self
+            .failures
+            .get(&client)
+            .is_some_and(|failures| failures.count >= MAX_FAILED_ATTEMPTS)
+        {
+            return Err(SelfCheckInError::TooManyAttempts);
+        }
+
+        let credential = credential.trim();
+        let accepted = [
+            Some(self.pin.as_str()),
+            self.previous_pin.as_deref(),
+            Some(self.token.as_str()),
+            self.previous_token.as_deref(),
+        ];
+        if !accepted.contains(&Some(credential)) {
+            let failures = self.failures.entry(client).or_insert(FailedAttempts {
+                count: 0,
+                last: now,
+            });
+            failures.count += 1;
+            failures.last = now;
+            return Err(if failures.count >= MAX_FAILED_ATTEMPTS {
+                SelfCheckInError::TooManyAttempts
+            } else {
+                SelfCheckInError::WrongPin
+            });
+        }
+        self.failures.remove(&client);
+
+        let identity = identity.trim();
+        if identity.is_empty() {
+            return Err(SelfCheckInError::EmptyName);
+        }
+        let identity = self.roster.name_for(identity).unwrap_or(identity);
+        let position = match table.active_positions_named(identity).as_slice() {
+            [] => return Err(SelfCheckInError::UnknownName),
+            [position] => *position,
+            _ => return Err(SelfCheckInError::AmbiguousName),
+        };
+        if self
+            .checked_in_by
+            .get(&client)
+            .is_some_and(|checked| *checked != position)
+        {
+            return Err(SelfCheckInError::DeviceAlreadyUsed);
+        }
+        let name = table
+            .subject_at(position)
+            .and_then(Subject::name)
+            .cloned()
+            .unwrap_or_default();
+
+        let changed = attendance.update_status(table, position, AttendanceStatus::Checked);
+        self.checked_in_by.insert(client, position);
+        Ok(SelfCheckInOutcome {
+            position,
+            name,
+            already_checked: !changed,
+        })
+    }
+
+    /// The check-in form, with the result of the last submission above it.
+    ///
+    /// With a `token` from the QR code the form carries it instead of asking for the PIN.
+    pub fn page(
+        result: Option<&Result<SelfCheckInOutcome, SelfCheckInError>>,
+        token: Option<&str>,
+    ) -> String {
+        let notice = match result {
+            None => String::new(),
+            Some(Ok(outcome)) if outcome.already_checked => format!(
+                r#"<p class="ok">{} is already checked in.</p>"#,
+                escape_html(&outcome.name)
+            ),
+            Some(Ok(outcome)) => format!(
+                r#"<p class="ok">Checked in: {}</p>"#,
+                escape_html(&outcome.name)
+            ),
+            Some(Err(error)) => format!(
+                r#"<p class="error">{}</p>"#,
+                escape_html(&error.to_string())
+            ),
+        };
+
+        let credential = match token {
+            Some(token) => format!(
+                r#"<input type="hidden" name="token" value="{}">"#,
+                escape_html(token)
+            ),
+            None => format!(
+                r#"<label for="pin">PIN shown on the screen</label>
+<input id="pin" name="pin" inputmode="numeric" maxlength="{PIN_LENGTH}" required>"#
+            ),
+        };
+
+        format!(
+            r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>Check in</title>
+<style>
+body {{ font-family: sans-serif; max-width: 22rem; margin: 2rem auto; padding: 0 1rem; }}
+label, input, button {{ display: block; width: 100%; box-sizing: border-box; font-size: 1.1rem; }}
+input {{ margin: 0.25rem 0 1rem; padding: 0.5rem; }}
+button {{ padding: 0.6rem; }}
+.ok {{ color: #1b7f3a; }}
+.error {{ color: #b3261e; }}
+</style>
+</head>
+<body>
+<h1>Check in</h1>
+{notice}
+<form method="post" action="/">
+<label for="name">Name or student ID</label>
+<input id="name" name="name" autocomplete="name" required>
+{credential}
+<button type="submit">Check in</button>
+</form>
+</body>
+</html>
+"#
+        )
+    }
+
+    fn generate_token() -> String {
+        rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(TOKEN_LENGTH)
+            .map(char::from)
+            .collect()
+    }
+
+    fn generate_pin() -> String {
+        let mut rng = rand::thread_rng();
+        (0..PIN_LENGTH)
+            .map(|_| char::from(b'0' + rng.gen_range(0..10)))
+            .collect()
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.chars()
+        .map(|character| match character {
+            '&' => "&amp;".to_owned(),
+            '<' => "&lt;".to_owned(),
+            '>' => "&gt;".to_owned(),
+            '"' => "&quot;".to_owned(),
+            '\'' => "&#39;".to_owned(),
+            other => other.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::{Ipv4Addr, TcpListener, TcpStream};
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::utilities::{parse_form_urlencoded, serve, HttpResponse};
+
+    fn classroom() -> (Table, Position) {
+        let position = Position { x: 1, y: 0 };
+        let table = Table::new(
+            2,
+            2,
+            vec![
+                (position, Subject::Some("Ann Lee".to_owned())),
+                (Position { x: 0, y: 1 }, Subject::Some("Bo Chen".to_owned())),
+            ],
+        );
+        (table, position)
+    }
+
+    fn post(address: std::net::SocketAddr, body: &str) -> String {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "POST / HTTP/1.1\r\nHost: {address}\r\n\
+             Content-Type: application/x-www-form-urlencoded\r\n\
+             Content-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn checks_in_by_student_id_over_http() {
+        let (table, position) = classroom();
+        let attendance = AttendanceBook::new(&table);
+        let mut gate = SelfCheckIn::new();
+        gate.set_roster(Roster::parse("20240017,Ann Lee").unwrap());
+        gate.set_open(true);
+        let pin = gate.pin().to_owned();
+        let state = Arc::new(Mutex::new((gate, table, attendance)));
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        let served = Arc::clone(&state);
+        serve(listener, move |request| {
+            let fields = parse_form_urlencoded(&request.body_text());
+            let field = |name: &str| fields.get(name).map(String::as_str).unwrap_or_default();
+            let (gate, table, attendance) = &mut *served.lock().unwrap();
+            let result = gate.check_in(
+                table,
+                attendance,
+                request.peer.unwrap(),
+                field("name"),
+                field("pin"),
+            );
+            let status = if result.is_ok() { 200 } else { 403 };
+            HttpResponse::html(status, SelfCheckIn::page(Some(&result), None))
+        });
+
+        let response = post(address, &format!("name=+20240017+&pin={pin}"));
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.contains("Checked in: Ann Lee"), "{response}");
+        let (_, _, attendance) = &*state.lock().unwrap();
+        assert_eq!(
+            attendance.status_at(position),
+            Some(AttendanceStatus::Checked)
+        );
+    }
+
+    #[test]
+    fn locks_out_a_device_after_repeated_wrong_pins() {
+        let (table, position) = classroom();
+        let mut attendance = AttendanceBook::new(&table);
+        let mut gate = SelfCheckIn::new();
+        gate.set_open(true);
+        let pin = gate.pin().to_owned();
+        let guesser = IpAddr::from([192, 168, 1, 20]);
+        let neighbour = IpAddr::from([192, 168, 1, 21]);
+
+        for _ in 1..MAX_FAILED_ATTEMPTS {
+            assert_eq!(
+                gate.check_in(&table, &mut attendance, guesser, "Ann Lee", "x"),
+                Err(SelfCheckInError::WrongPin)
+            );
+        }
+        assert_eq!(
+            gate.check_in(&table, &mut attendance, guesser, "Ann Lee", "x"),
+            Err(SelfCheckInError::TooManyAttempts)
+        );
+        assert_eq!(
+            gate.check_in(&table, &mut attendance, guesser, "Ann Lee", &pin),
+            Err(SelfCheckInError::TooManyAttempts)
+        );
+        assert!(gate
+            .check_in(&table, &mut attendance, neighbour, "ann lee", &pin)
+            .is_ok());
+        assert_eq!(
+            attendance.status_at(position),
+            Some(AttendanceStatus::Checked)
+        );
+    }
+
+    #[test]
+    fn checks_in_one_student_per_device() {
+        let (table, position) = classroom();
+        let mut attendance = AttendanceBook::new(&table);
+        let mut gate = SelfCheckIn::new();
+        gate.set_open(true);
+        let pin = gate.pin().to_owned();
+        let phone = IpAddr::from([192, 168, 1, 30]);
+
+        assert!(gate
+            .check_in(&table, &mut attendance, phone, "Ann Lee", &pin)
+            .is_ok());
+        assert!(gate
+            .check_in(&table, &mut attendance, phone, "Ann Lee", &pin)
+            .is_ok_and(|outcome| outcome.already_checked));
+        assert_eq!(
+            gate.check_in(&table, &mut attendance, phone, "Bo Chen", &pin),
+            Err(SelfCheckInError::DeviceAlreadyUsed)
+        );
+        assert_eq!(
+            attendance.status_at(Position { x: 0, y: 1 }),
+            Some(AttendanceStatus::Unchecked)
+        );
+        assert_eq!(
+            attendance.status_at(position),
+            Some(AttendanceStatus::Checked)
+        );
+
+        gate.set_open(false);
+        gate.set_open(true);
+        let pin = gate.pin().to_owned();
+        assert!(gate
+            .check_in(&table, &mut attendance, phone, "Bo Chen", &pin)
+            .is_ok());
+    }
+}
@@ -0,0 +1,195 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Local, NaiveDate, SecondsFormat};
+use serde::{Deserialize, Serialize};
+
+use super::config_store::write_file_atomic;
+use super::{AttendanceBook, AttendanceStatus, Subject, Table};
+
+/// How one person attended one session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionStatus {
+    Present,
+    Absent,
+    Leave,
+    Late,
+}
+
+impl SessionStatus {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Present => "present",
+            Self::Absent => "absent",
+            Self::Leave => "leave",
+            Self::Late => "late",
+        }
+    }
+}
+
+/// One named seat in a stored session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionEntry {
+    pub name: String,
+    pub x: u32,
+    pub y: u32,
+    /// Seat reference from the row and column labels; empty in older sessions.
+    #[serde(default)]
+    pub seat: String,
+    pub status: SessionStatus,
+    /// Note taken with the status, e.g. the reason for a leave.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Attendance of one check-in session, as stored in the sessions directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// RFC 3339 local time the session started.
+    pub started_at: String,
+    pub entries: Vec<SessionEntry>,
+}
+
+impl SessionRecord {
+    /// Snapshot of every named active seat.
+    ///
+    /// `Checked` seats count as late when they were checked in more than `late_after`
+    /// past `started_at`; `Marked` seats are on leave.
+    pub fn capture(
+        table: &Table,
+        attendance: &AttendanceBook,
+        started_at: SystemTime,
+        late_after: Option<Duration>,
+    ) -> Self {
+        let late_from = late_after.map(|grace| started_at + grace);
+        let entries = table
+            .iter_positions()
+            .filter(|position| !table.is_inert(*position))
+            .filter_map(|position| {
+                let name = table.subject_at(position).and_then(Subject::name)?;
+                if name.trim().is_empty() {
+                    return None;
+                }
+                let status = match attendance.status_at(position).unwrap_or_default() {
+                    AttendanceStatus::Checked => {
+                        let checked_at = attendance.checked_at(position);
+                        match (checked_at, late_from) {
+                            (Some(checked_at), Some(late_from)) if checked_at > late_from => {
+                                SessionStatus::Late
+                            }
+                            _ => SessionStatus::Present,
+                        }
+                    }
+                    AttendanceStatus::Unchecked => SessionStatus::Absent,
+                    AttendanceStatus::Marked => SessionStatus::Leave,
+                };
+                Some(SessionEntry {
+                    name: name.trim().to_owned(),
+                    x: position.x,
+                    y: position.y,
+                    seat: table.seat_label(position),
+                    status,
+                    note: attendance.note_at(position).map(str::to_owned),
+                })
+            })
+            .collect();
+
+        let started_at: DateTime<Local> = started_at.into();
+        Self {
+            started_at: started_at.to_rfc3339_opts(SecondsFormat::Secs, false),
+            entries,
+        }
+    }
+
+    pub fn started_at(&self) -> Option<DateTime<Local>> {
+        DateTime::parse_from_rfc3339(&self.started_at)
+            .ok()
+            .map(|time| time.with_timezone(&Local))
+    }
+
+    pub fn date(&self) -> Option<NaiveDate> {
+        self.started_at().map(|time| time.date_naive())
+    }
+
+    /// Share of people who were no longer pending, like
+    /// [`AttendanceStatistics::completed_ratio_percent`](super::AttendanceStatistics::completed_ratio_percent).
+    pub fn completion_percent(&self) -> u32 {
+        let completed = self
+            .entries
+            .iter()
+            .filter(|entry| entry.status != SessionStatus::Absent)
+            .count();
+        if self.entries.is_empty() {
+            0
+        } else {
+            (completed * 100 / self.entries.len()) as u32
+        }
+    }
+}
+
+/// Session records stored as one JSON file per session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionStore {
+    dir: PathBuf,
+}
+
+impl SessionStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Writes `record`, replacing an earlier save of the same session.
+    pub fn save(&self, record: &SessionRecord) -> io::Result<PathBuf> {
+        let started_at = record.started_at().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "session has no valid start time",
+            )
+        })?;
+        fs::create_dir_all(&self.dir)?;
+        let path = self
+            .dir
+            .join(format!("{}.json", started_at.format("%Y%m%d-%H%M%S")));
+        let json = serde_json::to_string_pretty(record)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        write_file_atomic(&path, json.as_bytes())?;
+        Ok(path)
+    }
+
+    /// Reads every stored session, oldest first. Unreadable files are reported and skipped.
+    pub fn load_all(&self) -> io::Result<Vec<SessionRecord>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "json")
+            })
+            .collect();
+        paths.sort();
+
+        let mut records = Vec::with_capacity(paths.len());
+        for path in paths {
+            let parsed = fs::read_to_string(&path)
+                .map_err(|error| error.to_string())
+                .and_then(|text| serde_json::from_str(&text).map_err(|error| error.to_string()));
+            match parsed {
+                Ok(record) => records.push(record),
+                Err(error) => eprintln!("skipping session {}: {error}", path.display()),
+            }
+        }
+        Ok(records)
+    }
+}
@@ -0,0 +1,509 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use super::{AppMode, ConfigError, Table};
+
+/// Command-line help, printed for `--help`.
+pub const USAGE: &str = "\
+Usage: checkin [validate] [OPTIONS]
+
+Commands:
+  validate                 Check the table config file and exit
+
+Options:
+  --config <FILE>          Table config file              [env: CHECKIN_CONFIG]
+  --settings <FILE>        Settings file                  [env: CHECKIN_SETTINGS]
+  --backups <N>            Config backups to keep, 0=off  [env: CHECKIN_BACKUPS]
+  --mode <MODE>            Initial mode: checkin, edit    [env: CHECKIN_MODE]
+  --layout <LAYOUT>        Layout without a config file:
+                           demo or <columns>x<rows>       [env: CHECKIN_LAYOUT]
+  --locale <LOCALE>        Export language: zh, en        [env: CHECKIN_LOCALE]
+  --theme <THEME>          system, light, dark, high-contrast, custom
+                                                          [env: CHECKIN_THEME]
+  --board <BOARD>          Seating board: auto, widgets, canvas
+                                                          [env: CHECKIN_BOARD]
+  --autosave <SECONDS>     Layout autosave interval, 0=off
+                                                          [env: CHECKIN_AUTOSAVE]
+  --export-template <TEXT> Statistics export template     [env: CHECKIN_EXPORT_TEMPLATE]
+  --api-port <PORT>        Serve the REST API on 127.0.0.1, 0=off
+                                                          [env: CHECKIN_API_PORT]
+  --self-checkin-port <PORT>
+                           Port of the self check-in page [env: CHECKIN_SELF_CHECKIN_PORT]
+  --roster <FILE>          Student ID to name list for card scans
+                                                          [env: CHECKIN_ROSTER]
+  --late-after <MINUTES>   Check-ins this long after the session start count as late
+                                                          [env: CHECKIN_LATE_AFTER]
+  -h, --help               Print this help
+
+Command-line options override environment variables, which override the settings file.
+";
+
+/// Layout used when no table config file exists yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DefaultLayout {
+    /// Randomized sample roster, see [`Table::default_table`].
+    #[default]
+    Demo,
+    Empty {
+        columns: u32,
+        rows: u32,
+    },
+}
+
+impl DefaultLayout {
+    pub fn build_table(self) -> Table {
+        match self {
+            Self::Demo => Table::default_table(),
+            Self::Empty { columns, rows } => Table::new(rows, columns, Vec::new()),
+        }
+    }
+}
+
+impl FromStr for DefaultLayout {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value == "demo" {
+            return Ok(Self::Demo);
+        }
+        let (columns, rows) = value.split_once('x').ok_or(())?;
+        let columns: u32 = columns.trim().parse().map_err(|_| ())?;
+        let rows: u32 = rows.trim().parse().map_err(|_| ())?;
+        if columns == 0 || rows == 0 {
+            return Err(());
+        }
+        Ok(Self::Empty { columns, rows })
+    }
+}
+
+/// Language for exported text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    Zh,
+    En,
+}
+
+impl FromStr for Locale {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "zh" | "zh-cn" | "zh_cn" => Ok(Self::Zh),
+            "en" | "en-us" | "en_us" => Ok(Self::En),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeChoice {
+    /// Follow the desktop's dark-mode preference.
+    #[default]
+    System,
+    Light,
+    Dark,
+    HighContrast,
+    /// Colors from the theme file in the config directory, see [`Theme`](super::Theme).
+    Custom,
+}
+
+impl FromStr for ThemeChoice {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "system" => Ok(Self::System),
+            "light" => Ok(Self::Light),
+            "dark" => Ok(Self::Dark),
+            "high-contrast" => Ok(Self::HighContrast),
+            "custom" => Ok(Self::Custom),
+            _ => Err(()),
+        }
+    }
+}
+
+/// How the seating board is built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoardChoice {
+    /// Widgets for ordinary rooms, the canvas past [`BoardChoice::AUTO_CANVAS_CELLS`].
+    #[default]
+    Auto,
+    /// One widget per cell.
+    Widgets,
+    /// A single drawn widget, for auditorium layouts with thousands of seats.
+    Canvas,
+}
+
+impl BoardChoice {
+    /// Table size from which `Auto` switches to the canvas.
+    pub const AUTO_CANVAS_CELLS: u32 = 600;
+
+    pub fn draws_canvas(self, table: &Table) -> bool {
+        match self {
+            Self::Auto => {
+                table.row_count().saturating_mul(table.column_count()) >= Self::AUTO_CANVAS_CELLS
+            }
+            Self::Widgets => false,
+            Self::Canvas => true,
+        }
+    }
+}
+
+impl FromStr for BoardChoice {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "auto" => Ok(Self::Auto),
+            "widgets" => Ok(Self::Widgets),
+            "canvas" => Ok(Self::Canvas),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SettingsError {
+    UnknownFlag(String),
+    MissingValue(String),
+    InvalidValue {
+        source: String,
+        value: String,
+        expected: &'static str,
+    },
+    UnknownFileKey {
+        file: PathBuf,
+        key: String,
+    },
+    File {
+        file: PathBuf,
+        error: ConfigError,
+    },
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownFlag(flag) => write!(f, "unknown option {flag}, see --help"),
+            Self::MissingValue(flag) => write!(f, "{flag} requires a value, see --help"),
+            Self::InvalidValue {
+                source,
+                value,
+                expected,
+            } => write!(
+                f,
+                "invalid value {value:?} for {source}, expected {expected}"
+            ),
+            Self::UnknownFileKey { file, key } => {
+                write!(f, "{}: unknown setting {key:?}", file.display())
+            }
+            Self::File { file, error } => write!(f, "{}: {error}", file.display()),
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+/// A setting that can come from any layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SettingKey {
+    ConfigFile,
+    SettingsFile,
+    Backups,
+    Mode,
+    Layout,
+    Locale,
+    Theme,
+    Board,
+    Autosave,
+    ExportTemplate,
+    ApiPort,
+    SelfCheckInPort,
+    Roster,
+    LateAfter,
+}
+
+impl SettingKey {
+    /// `(key, CLI flag, environment variable, settings file key)`.
+    const ALL: [(Self, &'static str, &'static str, &'static str); 14] = [
+        (
+            Self::ConfigFile,
+            "--config",
+            "CHECKIN_CONFIG",
+            "config_file",
+        ),
+        (Self::SettingsFile, "--settings", "CHECKIN_SETTINGS", ""),
+        (Self::Backups, "--backups", "CHECKIN_BACKUPS", "backups"),
+        (Self::Mode, "--mode", "CHECKIN_MODE", "mode"),
+        (Self::Layout, "--layout", "CHECKIN_LAYOUT", "layout"),
+        (Self::Locale, "--locale", "CHECKIN_LOCALE", "locale"),
+        (Self::Theme, "--theme", "CHECKIN_THEME", "theme"),
+        (Self::Board, "--board", "CHECKIN_BOARD", "board"),
+        (
+            Self::Autosave,
+            "--autosave",
+            "CHECKIN_AUTOSAVE",
+            "autosave_seconds",
+        ),
+        (
+            Self::ExportTemplate,
+            "--export-template",
+            "CHECKIN_EXPORT_TEMPLATE",
+            "export_template",
+        ),
+        (Self::ApiPort, "--api-port", "CHECKIN_API_PORT", "api_port"),
+        (
+            Self::SelfCheckInPort,
+            "--self-checkin-port",
+            "CHECKIN_SELF_CHECKIN_PORT",
+            "self_checkin_port",
+        ),
+        (Self::Roster, "--roster", "CHECKIN_ROSTER", "roster_file"),
+        (
+            Self::LateAfter,
+            "--late-after",
+            "CHECKIN_LATE_AFTER",
+            "late_after_minutes",
+        ),
+    ];
+
+    fn from_flag(flag: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|(_, name, _, _)| *name == flag)
+            .map(|(key, ..)| *key)
+    }
+
+    fn from_env(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|(_, _, env, _)| *env == name)
+            .map(|(key, ..)| *key)
+    }
+
+    fn from_file_key(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|(_, _, _, file_key)| !file_key.is_empty() && *file_key == name)
+            .map(|(key, ..)| *key)
+    }
+}
+
+/// One source of settings; `None` means the source leaves the value unset.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SettingsLayer {
+    pub config_file: Option<PathBuf>,
+    pub settings_file: Option<PathBuf>,
+    pub backup_count: Option<usize>,
+    pub initial_mode: Option<AppMode>,
+    pub default_layout: Option<DefaultLayout>,
+    pub locale: Option<Locale>,
+    pub theme: Option<ThemeChoice>,
+    pub board: Option<BoardChoice>,
+    pub autosave_interval: Option<Duration>,
+    pub export_template: Option<String>,
+    /// `Some(0)` explicitly disables the REST API.
+    pub api_port: Option<u16>,
+    pub self_checkin_port: Option<u16>,
+    pub roster_file: Option<PathBuf>,
+    pub late_after: Option<Duration>,
+}
+
+impl SettingsLayer {
+    /// Reads `CHECKIN_*` variables. Unknown `CHECKIN_*` names are reported as warnings.
+    pub fn from_env<I>(vars: I) -> Result<Self, SettingsError>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut layer = Self::default();
+        for (name, value) in vars {
+            if !name.starts_with("CHECKIN_") {
+                continue;
+            }
+            match SettingKey::from_env(&name) {
+                Some(key) => layer.set(key, &value, &name)?,
+                None => eprintln!("ignoring unknown environment variable {name}"),
+            }
+        }
+        Ok(layer)
+    }
+
+    /// Reads a JSON settings file. A missing file is an empty layer.
+    pub fn from_file(path: &Path) -> Result<Self, SettingsError> {
+        let file_error = |error: ConfigError| SettingsError::File {
+            file: path.to_path_buf(),
+            error,
+        };
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => return Err(file_error(error.into())),
+        };
+        let entries: BTreeMap<String, serde_json::Value> =
+            serde_json::from_str(&text).map_err(|error| file_error(error.into()))?;
+
+        let mut layer = Self::default();
+        for (name, value) in entries {
+            let key =
+                SettingKey::from_file_key(&name).ok_or_else(|| SettingsError::UnknownFileKey {
+                    file: path.to_path_buf(),
+                    key: name.clone(),
+                })?;
+            let value = match value {
+                serde_json::Value::String(text) => text,
+                other => other.to_string(),
+            };
+            let source = format!("{}: {name}", path.display());
+            layer.set(key, &value, &source)?;
+        }
+        Ok(layer)
+    }
+
+    /// Fills every unset value from `fallback`.
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            config_file: self.config_file.or(fallback.config_file),
+            settings_file: self.settings_file.or(fallback.settings_file),
+            backup_count: self.backup_count.or(fallback.backup_count),
+            initial_mode: self.initial_mode.or(fallback.initial_mode),
+            default_layout: self.default_layout.or(fallback.default_layout),
+            locale: self.locale.or(fallback.locale),
+            theme: self.theme.or(fallback.theme),
+            board: self.board.or(fallback.board),
+            autosave_interval: self.autosave_interval.or(fallback.autosave_interval),
+            export_template: self.export_template.or(fallback.export_template),
+            api_port: self.api_port.or(fallback.api_port),
+            self_checkin_port: self.self_checkin_port.or(fallback.self_checkin_port),
+            roster_file: self.roster_file.or(fallback.roster_file),
+            late_after: self.late_after.or(fallback.late_after),
+        }
+    }
+
+    fn set(&mut self, key: SettingKey, value: &str, source: &str) -> Result<(), SettingsError> {
+        let invalid = |expected: &'static str| SettingsError::InvalidValue {
+            source: source.to_owned(),
+            value: value.to_owned(),
+            expected,
+        };
+
+        match key {
+            SettingKey::ConfigFile => self.config_file = Some(PathBuf::from(value)),
+            SettingKey::SettingsFile => self.settings_file = Some(PathBuf::from(value)),
+            SettingKey::Backups => {
+                let count = value
+                    .parse()
+                    .map_err(|_| invalid("a non-negative number"))?;
+                self.backup_count = Some(count);
+            }
+            SettingKey::Mode => {
+                let mode = match value {
+                    "checkin" | "check-in" => AppMode::CheckIn,
+                    "edit" => AppMode::Edit,
+                    _ => return Err(invalid("checkin or edit")),
+                };
+                self.initial_mode = Some(mode);
+            }
+            SettingKey::Layout => {
+                let layout = value
+                    .parse()
+                    .map_err(|_| invalid("demo or <columns>x<rows>"))?;
+                self.default_layout = Some(layout);
+            }
+            SettingKey::Locale => {
+                self.locale = Some(value.parse().map_err(|_| invalid("zh or en"))?);
+            }
+            SettingKey::Theme => {
+                let theme = value
+                    .parse()
+                    .map_err(|_| invalid("system, light, dark, high-contrast or custom"))?;
+                self.theme = Some(theme);
+            }
+            SettingKey::Board => {
+                let board = value
+                    .parse()
+                    .map_err(|_| invalid("auto, widgets or canvas"))?;
+                self.board = Some(board);
+            }
+            SettingKey::Autosave => {
+                let seconds: u64 = value.parse().map_err(|_| invalid("a number of seconds"))?;
+                self.autosave_interval = Some(Duration::from_secs(seconds));
+            }
+            SettingKey::ExportTemplate => self.export_template = Some(value.to_owned()),
+            SettingKey::ApiPort => {
+                let port = value.parse().map_err(|_| invalid("a port number"))?;
+                self.api_port = Some(port);
+            }
+            SettingKey::SelfCheckInPort => {
+                let port = value.parse().map_err(|_| invalid("a port number"))?;
+                self.self_checkin_port = Some(port);
+            }
+            SettingKey::Roster => self.roster_file = Some(PathBuf::from(value)),
+            SettingKey::LateAfter => {
+                let seconds = value
+                    .parse::<u64>()
+                    .ok()
+                    .and_then(|minutes| minutes.checked_mul(60))
+                    .ok_or_else(|| invalid("a number of minutes"))?;
+                self.late_after = Some(Duration::from_secs(seconds));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What the command line asked for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliCommand {
+    Run(SettingsLayer),
+    Validate(SettingsLayer),
+    Help,
+}
+
+impl CliCommand {
+    /// Parses arguments without the program name. Accepts `--flag value` and `--flag=value`.
+    pub fn parse(args: &[String]) -> Result<Self, SettingsError> {
+        let (validate, args) = match args.split_first() {
+            Some((first, rest)) if first == "validate" => (true, rest),
+            _ => (false, args),
+        };
+
+        let mut layer = SettingsLayer::default();
+        let mut index = 0usize;
+        while index < args.len() {
+            let arg = &args[index];
+            if arg == "-h" || arg == "--help" {
+                return Ok(Self::Help);
+            }
+
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+                _ => (arg.as_str(), None),
+            };
+            let key = SettingKey::from_flag(flag)
+                .ok_or_else(|| SettingsError::UnknownFlag(arg.clone()))?;
+            let value = match inline_value {
+                Some(value) => value,
+                None => {
+                    index += 1;
+                    args.get(index)
+                        .map(String::as_str)
+                        .ok_or_else(|| SettingsError::MissingValue(flag.to_owned()))?
+                }
+            };
+            layer.set(key, value, flag)?;
+            index += 1;
+        }
+
+        Ok(if validate {
+            Self::Validate(layer)
+        } else {
+            Self::Run(layer)
+        })
+    }
+}
@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
 use std::io;
 use std::path::Path;
@@ -7,14 +8,62 @@ use rand::prelude::SliceRandom;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+use super::config_migration::{migrate_to_current, CONFIG_VERSION};
+use super::config_store::write_file_atomic;
+use super::{ConfigBackups, ConfigError, GridText};
+
 /// A rectangular table layout.
 /// `subjects` only stores explicitly assigned positions.
 /// Any missing position is treated as an empty active seat.
-#[derive(Debug, Clone)]
+/// `groups` tags positions with lab groups or sections; a seat keeps its groups when
+/// its subject changes.
+/// `row_labels` and `column_labels` hold one entry per row/column, empty when unlabeled.
+/// `spans` holds merged cells by their top-left position; the other positions they
+/// cover are not cells of their own.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Table {
     row_count: u32,
     column_count: u32,
     subjects: HashMap<Position, Subject>,
+    groups: HashMap<Position, Vec<String>>,
+    row_labels: Vec<String>,
+    column_labels: Vec<String>,
+    spans: HashMap<Position, CellSpan>,
+    /// Running totals of inert subjects, kept in step with `subjects`.
+    counts: SubjectCounts,
+}
+
+/// Number of blocked and transparent subjects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct SubjectCounts {
+    blocked: u32,
+    transparent: u32,
+}
+
+impl SubjectCounts {
+    fn recount(subjects: &HashMap<Position, Subject>) -> Self {
+        let mut counts = Self::default();
+        for subject in subjects.values() {
+            counts.add(subject);
+        }
+        counts
+    }
+
+    fn add(&mut self, subject: &Subject) {
+        match subject {
+            Subject::Block(_) => self.blocked += 1,
+            Subject::Transparent => self.transparent += 1,
+            Subject::Some(_) => {}
+        }
+    }
+
+    fn remove(&mut self, subject: &Subject) {
+        match subject {
+            Subject::Block(_) => self.blocked -= 1,
+            Subject::Transparent => self.transparent -= 1,
+            Subject::Some(_) => {}
+        }
+    }
 }
 
 /// Zero-based table coordinate.
@@ -53,6 +102,29 @@ impl Subject {
     }
 }
 
+/// Size of a cell in grid positions; larger than 1x1 for merged cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellSpan {
+    pub columns: u32,
+    pub rows: u32,
+}
+
+impl CellSpan {
+    pub const SINGLE: Self = Self {
+        columns: 1,
+        rows: 1,
+    };
+
+    pub const fn is_single(self) -> bool {
+        self.columns <= 1 && self.rows <= 1
+    }
+
+    fn contains(self, anchor: Position, position: Position) -> bool {
+        (anchor.x..anchor.x + self.columns).contains(&position.x)
+            && (anchor.y..anchor.y + self.rows).contains(&position.y)
+    }
+}
+
 /// Canonical domain kind for each table cell.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CellKind {
@@ -73,10 +145,16 @@ impl Table {
             }
         }
 
+        let counts = SubjectCounts::recount(&subject_map);
         Self {
             row_count,
             column_count,
             subjects: subject_map,
+            groups: HashMap::new(),
+            row_labels: vec![String::new(); row_count as usize],
+            column_labels: vec![String::new(); column_count as usize],
+            spans: HashMap::new(),
+            counts,
         }
     }
 
@@ -105,7 +183,7 @@ impl Table {
     }
 
     pub fn set_subject(&mut self, position: Position, subject: Option<Subject>) -> bool {
-        if !self.contains(position) {
+        if !self.contains(position) || self.is_covered(position) {
             return false;
         }
 
@@ -114,21 +192,212 @@ impl Table {
             return false;
         }
 
-        match normalized {
-            Some(subject) => {
-                self.subjects.insert(position, subject);
+        self.remove_subject(position);
+        if let Some(subject) = normalized {
+            self.insert_subject(position, subject);
+        }
+        true
+    }
+
+    pub fn row_label(&self, row_index: u32) -> Option<&str> {
+        Self::label_at(&self.row_labels, row_index)
+    }
+
+    pub fn column_label(&self, column_index: u32) -> Option<&str> {
+        Self::label_at(&self.column_labels, column_index)
+    }
+
+    pub fn has_row_labels(&self) -> bool {
+        self.row_labels.iter().any(|label| !label.is_empty())
+    }
+
+    pub fn has_column_labels(&self) -> bool {
+        self.column_labels.iter().any(|label| !label.is_empty())
+    }
+
+    pub fn set_row_label(&mut self, row_index: u32, label: Option<String>) -> bool {
+        Self::set_label_at(&mut self.row_labels, row_index, label)
+    }
+
+    pub fn set_column_label(&mut self, column_index: u32, label: Option<String>) -> bool {
+        Self::set_label_at(&mut self.column_labels, column_index, label)
+    }
+
+    /// Human-readable seat reference, e.g. `Row A, Window side` or `row 2, seat 3`.
+    ///
+    /// Unlabeled rows and columns fall back to one-based numbers.
+    pub fn seat_label(&self, position: Position) -> String {
+        let row = self
+            .row_label(position.y)
+            .map_or_else(|| format!("row {}", position.y + 1), str::to_owned);
+        let column = self
+            .column_label(position.x)
+            .map_or_else(|| format!("seat {}", position.x + 1), str::to_owned);
+        format!("{row}, {column}")
+    }
+
+    pub fn groups_at(&self, position: Position) -> &[String] {
+        self.groups.get(&position).map_or(&[], Vec::as_slice)
+    }
+
+    /// Replaces the groups of a seat.
+    ///
+    /// Names are trimmed, and empty or repeated names are dropped.
+    pub fn set_groups(&mut self, position: Position, groups: Vec<String>) -> bool {
+        if !self.contains(position) || self.is_covered(position) {
+            return false;
+        }
+
+        let mut normalized: Vec<String> = Vec::with_capacity(groups.len());
+        for group in groups {
+            let group = group.trim();
+            if !group.is_empty() && !normalized.iter().any(|existing| existing == group) {
+                normalized.push(group.to_owned());
             }
-            None => {
-                self.subjects.remove(&position);
+        }
+        if self.groups_at(position) == normalized.as_slice() {
+            return false;
+        }
+
+        if normalized.is_empty() {
+            self.groups.remove(&position);
+        } else {
+            self.groups.insert(position, normalized);
+        }
+        true
+    }
+
+    /// Every group used in the table, sorted.
+    pub fn group_names(&self) -> Vec<String> {
+        let names: BTreeSet<&String> = self.groups.values().flatten().collect();
+        names.into_iter().cloned().collect()
+    }
+
+    pub fn positions_in_group(&self, group: &str) -> Vec<Position> {
+        self.iter_positions()
+            .filter(|position| self.groups_at(*position).iter().any(|name| name == group))
+            .collect()
+    }
+
+    /// Size of the cell at `position`; only the top-left position of a merged cell
+    /// reports its full span.
+    pub fn span_at(&self, position: Position) -> CellSpan {
+        self.spans
+            .get(&position)
+            .copied()
+            .unwrap_or(CellSpan::SINGLE)
+    }
+
+    /// Merged cells by top-left position.
+    pub fn merged_cells(&self) -> impl Iterator<Item = (Position, CellSpan)> + '_ {
+        self.spans.iter().map(|(anchor, span)| (*anchor, *span))
+    }
+
+    /// Top-left position of the cell covering `position`, which is `position` itself
+    /// unless it lies inside a merged cell.
+    pub fn cell_origin(&self, position: Position) -> Position {
+        self.merged_cells()
+            .find(|(anchor, span)| span.contains(*anchor, position))
+            .map_or(position, |(anchor, _)| anchor)
+    }
+
+    /// Whether `position` lies inside a merged cell without being its top-left corner.
+    pub fn is_covered(&self, position: Position) -> bool {
+        self.cell_origin(position) != position
+    }
+
+    /// Merges the rectangle between two corners into one cell.
+    ///
+    /// The rectangle grows to take in merged cells it partly overlaps. The new cell
+    /// keeps the first subject and groups found in reading order; the rest are cleared.
+    /// Returns `false` when nothing changed.
+    pub fn merge_cells(&mut self, corner: Position, opposite: Position) -> bool {
+        if !self.contains(corner) || !self.contains(opposite) {
+            return false;
+        }
+
+        let (mut min, mut max) = (
+            Position {
+                x: corner.x.min(opposite.x),
+                y: corner.y.min(opposite.y),
+            },
+            Position {
+                x: corner.x.max(opposite.x),
+                y: corner.y.max(opposite.y),
+            },
+        );
+        loop {
+            let mut grown = false;
+            for (anchor, span) in self.merged_cells().collect::<Vec<_>>() {
+                let far = Position {
+                    x: anchor.x + span.columns - 1,
+                    y: anchor.y + span.rows - 1,
+                };
+                let overlaps =
+                    anchor.x <= max.x && far.x >= min.x && anchor.y <= max.y && far.y >= min.y;
+                if overlaps
+                    && (anchor.x < min.x || anchor.y < min.y || far.x > max.x || far.y > max.y)
+                {
+                    min = Position {
+                        x: min.x.min(anchor.x),
+                        y: min.y.min(anchor.y),
+                    };
+                    max = Position {
+                        x: max.x.max(far.x),
+                        y: max.y.max(far.y),
+                    };
+                    grown = true;
+                }
             }
+            if !grown {
+                break;
+            }
+        }
+
+        let span = CellSpan {
+            columns: max.x - min.x + 1,
+            rows: max.y - min.y + 1,
+        };
+        if span.is_single() || self.span_at(min) == span {
+            return false;
+        }
+
+        let area: Vec<Position> = self
+            .iter_positions()
+            .filter(|position| span.contains(min, *position))
+            .collect();
+        let subject = area
+            .iter()
+            .find_map(|position| self.subjects.get(position).cloned());
+        let groups = area
+            .iter()
+            .find_map(|position| self.groups.get(position).cloned());
+        for position in &area {
+            self.remove_subject(*position);
+            self.groups.remove(position);
+            self.spans.remove(position);
+        }
+        if let Some(subject) = subject {
+            self.insert_subject(min, subject);
         }
+        self.groups.extend(groups.map(|groups| (min, groups)));
+        self.spans.insert(min, span);
         true
     }
 
+    /// Splits the merged cell covering `position` back into single cells.
+    pub fn split_cell(&mut self, position: Position) -> bool {
+        self.spans.remove(&self.cell_origin(position)).is_some()
+    }
+
     pub fn cell_kind(&self, position: Position) -> Option<CellKind> {
         if !self.contains(position) {
             return None;
         }
+        if self.is_covered(position) {
+            // Covered positions are part of another cell, so they count like gaps.
+            return Some(CellKind::Transparent);
+        }
 
         let kind = match self.subject_at(position) {
             Some(subject) if subject.is_transparent() => CellKind::Transparent,
@@ -146,22 +415,34 @@ impl Table {
     }
 
     pub fn blocked_cells(&self) -> u32 {
-        self.subjects
-            .values()
-            .filter(|subject| subject.is_blocked())
-            .count() as u32
+        self.subject_counts().blocked
     }
 
     pub fn transparent_cells(&self) -> u32 {
-        self.subjects
-            .values()
-            .filter(|subject| subject.is_transparent())
-            .count() as u32
+        self.subject_counts().transparent
+    }
+
+    /// The running counts; debug builds check them against a full recount.
+    fn subject_counts(&self) -> SubjectCounts {
+        debug_assert_eq!(
+            self.counts,
+            SubjectCounts::recount(&self.subjects),
+            "subject counters drifted from a full recount"
+        );
+        self.counts
     }
 
     pub fn active_cells(&self) -> u32 {
         self.total_cells()
-            .saturating_sub(self.blocked_cells() + self.transparent_cells())
+            .saturating_sub(self.blocked_cells() + self.transparent_cells() + self.covered_cells())
+    }
+
+    /// Positions taken up by merged cells beyond their top-left corner.
+    pub fn covered_cells(&self) -> u32 {
+        self.spans
+            .values()
+            .map(|span| span.columns * span.rows - 1)
+            .sum()
     }
 
     pub fn iter_positions(&self) -> impl Iterator<Item = Position> + '_ {
@@ -169,12 +450,29 @@ impl Table {
             .flat_map(move |y| (0..self.column_count).map(move |x| Position { x, y }))
     }
 
+    /// Active seats named `name`, ignoring case and surrounding whitespace.
+    pub fn active_positions_named(&self, name: &str) -> Vec<Position> {
+        let wanted = name.trim().to_lowercase();
+        self.iter_positions()
+            .filter(|position| !self.is_inert(*position))
+            .filter(|position| {
+                self.subject_at(*position)
+                    .and_then(Subject::name)
+                    .is_some_and(|name| name.trim().to_lowercase() == wanted)
+            })
+            .collect()
+    }
+
     pub fn add_row(&mut self) {
         self.row_count = self.row_count.saturating_add(1);
+        self.row_labels
+            .resize(self.row_count as usize, String::new());
     }
 
     pub fn add_column(&mut self) {
         self.column_count = self.column_count.saturating_add(1);
+        self.column_labels
+            .resize(self.column_count as usize, String::new());
     }
 
     pub fn remove_row(&mut self, row_index: u32) -> bool {
@@ -182,21 +480,22 @@ impl Table {
             return false;
         }
 
-        let mut next = HashMap::with_capacity(self.subjects.len());
-        for (position, subject) in std::mem::take(&mut self.subjects) {
-            if position.y == row_index {
-                continue;
-            }
-
-            let y = if position.y > row_index {
-                position.y - 1
-            } else {
-                position.y
-            };
-            next.insert(Position { x: position.x, y }, subject);
+        let shift = |position: Position| match position.y.cmp(&row_index) {
+            Ordering::Less => Some(position),
+            Ordering::Equal => None,
+            Ordering::Greater => Some(Position {
+                x: position.x,
+                y: position.y - 1,
+            }),
+        };
+        self.shrink_spans(Axis::Row, row_index);
+        for x in 0..self.column_count {
+            self.remove_subject(Position { x, y: row_index });
         }
-
-        self.subjects = next;
+        shift_positions(&mut self.subjects, shift);
+        shift_positions(&mut self.groups, shift);
+        shift_positions(&mut self.spans, shift);
+        self.row_labels.remove(row_index as usize);
         self.row_count -= 1;
         true
     }
@@ -206,41 +505,83 @@ impl Table {
             return false;
         }
 
-        let mut next = HashMap::with_capacity(self.subjects.len());
-        for (position, subject) in std::mem::take(&mut self.subjects) {
-            if position.x == column_index {
-                continue;
-            }
-
-            let x = if position.x > column_index {
-                position.x - 1
-            } else {
-                position.x
-            };
-            next.insert(Position { x, y: position.y }, subject);
+        let shift = |position: Position| match position.x.cmp(&column_index) {
+            Ordering::Less => Some(position),
+            Ordering::Equal => None,
+            Ordering::Greater => Some(Position {
+                x: position.x - 1,
+                y: position.y,
+            }),
+        };
+        self.shrink_spans(Axis::Column, column_index);
+        for y in 0..self.row_count {
+            self.remove_subject(Position { x: column_index, y });
         }
-
-        self.subjects = next;
+        shift_positions(&mut self.subjects, shift);
+        shift_positions(&mut self.groups, shift);
+        shift_positions(&mut self.spans, shift);
+        self.column_labels.remove(column_index as usize);
         self.column_count -= 1;
         true
     }
 
+    /// Writes a pasted grid with its top-left corner at `origin`.
+    ///
+    /// Rows and columns are appended when the grid extends past the current bounds.
+    /// Returns `true` when the table size or any subject changed.
+    pub fn paste_grid(&mut self, origin: Position, grid: &GridText) -> bool {
+        if !self.contains(origin) || grid.is_empty() {
+            return false;
+        }
+
+        let mut changed = false;
+        let required_rows = origin.y.saturating_add(grid.row_count());
+        while self.row_count < required_rows {
+            self.add_row();
+            changed = true;
+        }
+        let required_columns = origin.x.saturating_add(grid.column_count());
+        while self.column_count < required_columns {
+            self.add_column();
+            changed = true;
+        }
+
+        for (offset, subject) in grid.iter_cells() {
+            let position = Position {
+                x: origin.x + offset.x,
+                y: origin.y + offset.y,
+            };
+            changed |= self.set_subject(position, subject.cloned());
+        }
+        changed
+    }
+
+    /// Atomically replaces the config file with this table.
     pub fn write_config(&self, config_file: &Path) -> io::Result<()> {
         let payload = AppConfigFile {
+            version: CONFIG_VERSION,
             default_table: TableConfig::from_table(self),
         };
         let text = serde_json::to_string_pretty(&payload).map_err(io::Error::other)?;
-        fs::write(config_file, text)
+        write_file_atomic(config_file, text.as_bytes())
     }
 
-    pub fn load_config(config_file: &Path) -> io::Result<Self> {
-        let text = fs::read_to_string(config_file)?;
-        if let Ok(payload) = serde_json::from_str::<AppConfigFile>(&text) {
-            return Ok(payload.default_table.into_table());
-        }
+    /// Backs up the current config file before writing, so earlier layouts can be restored.
+    pub fn write_config_with_backup(
+        &self,
+        config_file: &Path,
+        backups: &ConfigBackups,
+    ) -> io::Result<()> {
+        backups.backup(config_file)?;
+        self.write_config(config_file)
+    }
 
-        let payload: TableConfig = serde_json::from_str(&text).map_err(io::Error::other)?;
-        Ok(payload.into_table())
+    /// Loads a table config, migrating older layouts to the current schema first.
+    pub fn load_config(config_file: &Path) -> Result<Self, ConfigError> {
+        let text = fs::read_to_string(config_file)?;
+        let value = migrate_to_current(serde_json::from_str(&text)?)?;
+        let payload: AppConfigFile = serde_json::from_value(value)?;
+        Ok(payload.default_table.into_table())
     }
 
     pub fn default_table() -> Self {
@@ -275,6 +616,76 @@ impl Table {
         Self::new(ROW_COUNT, COLUMN_COUNT, subjects)
     }
 
+    /// Shrinks merged cells crossing a row or column that is about to be removed.
+    ///
+    /// A cell whose top-left corner sits on the removed line moves its subject and
+    /// groups one step along, so they survive the shift. Cells only one line thick
+    /// there are removed together with the line.
+    fn shrink_spans(&mut self, axis: Axis, index: u32) {
+        for (anchor, mut span) in std::mem::take(&mut self.spans) {
+            let start = axis.coordinate(anchor);
+            let length = axis.length(&mut span);
+            if !(start..start + *length).contains(&index) {
+                self.spans.insert(anchor, span);
+                continue;
+            }
+            if *length == 1 {
+                continue;
+            }
+
+            *length -= 1;
+            let anchor = if start == index {
+                let next = axis.step(anchor);
+                if let Some(subject) = self.remove_subject(anchor) {
+                    self.insert_subject(next, subject);
+                }
+                if let Some(groups) = self.groups.remove(&anchor) {
+                    self.groups.insert(next, groups);
+                }
+                next
+            } else {
+                anchor
+            };
+            if !span.is_single() {
+                self.spans.insert(anchor, span);
+            }
+        }
+    }
+
+    fn insert_subject(&mut self, position: Position, subject: Subject) {
+        self.counts.add(&subject);
+        if let Some(replaced) = self.subjects.insert(position, subject) {
+            self.counts.remove(&replaced);
+        }
+    }
+
+    fn remove_subject(&mut self, position: Position) -> Option<Subject> {
+        let removed = self.subjects.remove(&position);
+        if let Some(subject) = &removed {
+            self.counts.remove(subject);
+        }
+        removed
+    }
+
+    fn label_at(labels: &[String], index: u32) -> Option<&str> {
+        labels
+            .get(index as usize)
+            .map(String::as_str)
+            .filter(|label| !label.is_empty())
+    }
+
+    fn set_label_at(labels: &mut [String], index: u32, label: Option<String>) -> bool {
+        let Some(slot) = labels.get_mut(index as usize) else {
+            return false;
+        };
+        let label = label.as_deref().map(str::trim).unwrap_or_default();
+        if slot == label {
+            return false;
+        }
+        *slot = label.to_owned();
+        true
+    }
+
     fn normalize_subject(subject: Option<Subject>) -> Option<Subject> {
         match subject {
             Some(Subject::Some(name)) => {
@@ -292,54 +703,167 @@ impl Table {
     }
 }
 
+/// Direction of a row or column removal.
+#[derive(Debug, Clone, Copy)]
+enum Axis {
+    Row,
+    Column,
+}
+
+impl Axis {
+    const fn coordinate(self, position: Position) -> u32 {
+        match self {
+            Self::Row => position.y,
+            Self::Column => position.x,
+        }
+    }
+
+    fn length(self, span: &mut CellSpan) -> &mut u32 {
+        match self {
+            Self::Row => &mut span.rows,
+            Self::Column => &mut span.columns,
+        }
+    }
+
+    /// The next position across the line, away from the top-left corner.
+    const fn step(self, position: Position) -> Position {
+        match self {
+            Self::Row => Position {
+                x: position.x,
+                y: position.y + 1,
+            },
+            Self::Column => Position {
+                x: position.x + 1,
+                y: position.y,
+            },
+        }
+    }
+}
+
+/// Re-keys `map` after a row or column removal; `shift` returns `None` for removed positions.
+fn shift_positions<T>(
+    map: &mut HashMap<Position, T>,
+    shift: impl Fn(Position) -> Option<Position>,
+) {
+    *map = std::mem::take(map)
+        .into_iter()
+        .filter_map(|(position, value)| shift(position).map(|position| (position, value)))
+        .collect();
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct AppConfigFile {
-    default_table: TableConfig,
+pub(super) struct AppConfigFile {
+    pub(super) version: u32,
+    pub(super) default_table: TableConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct TableConfig {
-    row_count: u32,
-    column_count: u32,
-    subjects: Vec<TableConfigSubject>,
+pub(super) struct TableConfig {
+    pub(super) row_count: u32,
+    pub(super) column_count: u32,
+    pub(super) subjects: Vec<TableConfigSubject>,
+    /// One label per row from the top; missing or empty entries are unlabeled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) row_labels: Vec<String>,
+    /// One label per column from the left; missing or empty entries are unlabeled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) column_labels: Vec<String>,
+    /// Cells spanning more than one position; positions they cover keep no subject.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) merged_cells: Vec<TableConfigSpan>,
 }
 
 impl TableConfig {
     fn from_table(table: &Table) -> Self {
         let mut subjects = Vec::new();
         for position in table.iter_positions() {
-            if let Some(subject) = table.subject_at(position) {
-                subjects.push(TableConfigSubject::from_subject(position, subject));
+            let groups = table.groups_at(position);
+            match table.subject_at(position) {
+                Some(subject) => {
+                    subjects.push(TableConfigSubject::from_subject(position, subject, groups));
+                }
+                None if !groups.is_empty() => {
+                    subjects.push(TableConfigSubject::from_groups(position, groups));
+                }
+                None => {}
             }
         }
 
+        let labels = |labels: &[String]| {
+            let used = labels
+                .iter()
+                .rposition(|label| !label.is_empty())
+                .map_or(0, |last| last + 1);
+            labels[..used].to_vec()
+        };
+
         Self {
             row_count: table.row_count(),
             column_count: table.column_count(),
             subjects,
+            row_labels: labels(&table.row_labels),
+            column_labels: labels(&table.column_labels),
+            merged_cells: table
+                .iter_positions()
+                .filter_map(|position| {
+                    let span = table.span_at(position);
+                    (!span.is_single()).then_some(TableConfigSpan {
+                        x: position.x,
+                        y: position.y,
+                        columns: span.columns,
+                        rows: span.rows,
+                    })
+                })
+                .collect(),
         }
     }
 
     fn into_table(self) -> Table {
-        let subjects = self
-            .subjects
-            .into_iter()
-            .map(TableConfigSubject::into_subject)
-            .collect();
-        Table::new(self.row_count, self.column_count, subjects)
+        let mut subjects = Vec::with_capacity(self.subjects.len());
+        let mut groups = Vec::new();
+        for entry in self.subjects {
+            let (position, subject, entry_groups) = entry.into_subject();
+            subjects.extend(subject.map(|subject| (position, subject)));
+            groups.push((position, entry_groups));
+        }
+
+        let mut table = Table::new(self.row_count, self.column_count, subjects);
+        for (position, entry_groups) in groups {
+            table.set_groups(position, entry_groups);
+        }
+        for (row_index, label) in (0..).zip(self.row_labels) {
+            table.set_row_label(row_index, Some(label));
+        }
+        for (column_index, label) in (0..).zip(self.column_labels) {
+            table.set_column_label(column_index, Some(label));
+        }
+        for merged in self.merged_cells {
+            let anchor = Position {
+                x: merged.x,
+                y: merged.y,
+            };
+            let far = Position {
+                x: merged.x.saturating_add(merged.columns.max(1) - 1),
+                y: merged.y.saturating_add(merged.rows.max(1) - 1),
+            };
+            table.merge_cells(anchor, far);
+        }
+        table
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct TableConfigSubject {
-    x: u32,
-    y: u32,
-    kind: TableConfigCellKind,
-    name: Option<String>,
+pub(super) struct TableConfigSubject {
+    pub(super) x: u32,
+    pub(super) y: u32,
+    pub(super) kind: TableConfigCellKind,
+    pub(super) name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) groups: Vec<String>,
 }
 
 impl TableConfigSubject {
-    fn from_subject(position: Position, subject: &Subject) -> Self {
+    fn from_subject(position: Position, subject: &Subject, groups: &[String]) -> Self {
         let (kind, name) = match subject {
             Subject::Transparent => (TableConfigCellKind::Transparent, None),
             Subject::Block(name) => (TableConfigCellKind::Blocked, Some(name.clone())),
@@ -351,14 +875,27 @@ impl TableConfigSubject {
             y: position.y,
             kind,
             name,
+            groups: groups.to_vec(),
+        }
+    }
+
+    /// An empty active seat that only carries group tags.
+    fn from_groups(position: Position, groups: &[String]) -> Self {
+        Self {
+            x: position.x,
+            y: position.y,
+            kind: TableConfigCellKind::Active,
+            name: None,
+            groups: groups.to_vec(),
         }
     }
 
-    fn into_subject(self) -> (Position, Subject) {
+    /// An active entry without a name is an empty seat, so it yields no subject.
+    fn into_subject(self) -> (Position, Option<Subject>, Vec<String>) {
         let subject = match self.kind {
-            TableConfigCellKind::Transparent => Subject::Transparent,
-            TableConfigCellKind::Blocked => Subject::Block(self.name.unwrap_or_default()),
-            TableConfigCellKind::Active => Subject::Some(self.name.unwrap_or_default()),
+            TableConfigCellKind::Transparent => Some(Subject::Transparent),
+            TableConfigCellKind::Blocked => Some(Subject::Block(self.name.unwrap_or_default())),
+            TableConfigCellKind::Active => self.name.map(Subject::Some),
         };
 
         (
@@ -367,13 +904,23 @@ impl TableConfigSubject {
                 y: self.y,
             },
             subject,
+            self.groups,
         )
     }
 }
 
+/// A merged cell, by its top-left position and size.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub(super) struct TableConfigSpan {
+    pub(super) x: u32,
+    pub(super) y: u32,
+    pub(super) columns: u32,
+    pub(super) rows: u32,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
-enum TableConfigCellKind {
+pub(super) enum TableConfigCellKind {
     Active,
     Blocked,
     Transparent,
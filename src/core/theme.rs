@@ -0,0 +1,354 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{AttendanceStatus, ConfigError, ThemeChoice};
+
+/// An sRGB color with alpha, written as `#rrggbb` or `#rrggbbaa`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Rgba {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    pub alpha: f64,
+}
+
+impl Rgba {
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha: 1.0,
+        }
+    }
+
+    pub const fn with_alpha(self, alpha: f64) -> Self {
+        Self { alpha, ..self }
+    }
+
+    /// Channels as `0.0..=1.0` fractions, the form cairo takes.
+    pub fn fractions(self) -> (f64, f64, f64, f64) {
+        (
+            f64::from(self.red) / 255.0,
+            f64::from(self.green) / 255.0,
+            f64::from(self.blue) / 255.0,
+            self.alpha,
+        )
+    }
+}
+
+impl fmt::Display for Rgba {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)?;
+        if self.alpha < 1.0 {
+            write!(
+                f,
+                "{:02x}",
+                (self.alpha.clamp(0.0, 1.0) * 255.0).round() as u8
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Rgba {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid color {value:?}, expected #rrggbb or #rrggbbaa");
+        let digits = value
+            .trim()
+            .strip_prefix('#')
+            .filter(|digits| matches!(digits.len(), 6 | 8) && digits.is_ascii())
+            .ok_or_else(invalid)?;
+        let channel = |index: usize| {
+            u8::from_str_radix(&digits[index * 2..index * 2 + 2], 16).map_err(|_| invalid())
+        };
+        let alpha = if digits.len() == 8 {
+            f64::from(channel(3)?) / 255.0
+        } else {
+            1.0
+        };
+        Ok(Self::rgb(channel(0)?, channel(1)?, channel(2)?).with_alpha(alpha))
+    }
+}
+
+impl TryFrom<String> for Rgba {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Rgba> for String {
+    fn from(color: Rgba) -> Self {
+        color.to_string()
+    }
+}
+
+/// Fill, border and text color of one kind of seat.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeatColors {
+    pub background: Rgba,
+    pub border: Rgba,
+    pub foreground: Rgba,
+}
+
+/// Every color the app paints, for the CSS and the drawn widgets alike.
+///
+/// A custom theme is a JSON file naming a built-in `base` and overriding any of these
+/// fields, e.g. `{"base": "dark", "checked": {"background": "#166534"}}`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Theme {
+    /// Asks GTK for its dark style, so stock widgets match.
+    pub dark: bool,
+    pub window_background: Rgba,
+    pub panel_background: Rgba,
+    pub panel_border: Rgba,
+    pub text: Rgba,
+    pub secondary_text: Rgba,
+    /// Captions, and the dash on seats nobody is assigned to.
+    pub muted_text: Rgba,
+    pub success_text: Rgba,
+    pub error_text: Rgba,
+    pub warning_background: Rgba,
+    pub warning_border: Rgba,
+    pub error_background: Rgba,
+    pub error_border: Rgba,
+    pub checked: SeatColors,
+    pub unchecked: SeatColors,
+    pub marked: SeatColors,
+    pub blocked: SeatColors,
+    /// Active seats in edit mode.
+    pub edit_pending: SeatColors,
+    pub selected: Rgba,
+    /// Outline of the seat called by the roll call or random picker.
+    pub highlighted: Rgba,
+}
+
+impl Theme {
+    pub const LIGHT: Self = Self {
+        dark: false,
+        window_background: Rgba::rgb(208, 208, 208),
+        panel_background: Rgba::rgb(248, 250, 252).with_alpha(0.8),
+        panel_border: Rgba::rgb(148, 163, 184).with_alpha(0.4),
+        text: Rgba::rgb(15, 23, 42),
+        secondary_text: Rgba::rgb(51, 65, 85),
+        muted_text: Rgba::rgb(100, 116, 139),
+        success_text: Rgba::rgb(21, 128, 61),
+        error_text: Rgba::rgb(185, 28, 28),
+        warning_background: Rgba::rgb(254, 249, 195).with_alpha(0.9),
+        warning_border: Rgba::rgb(202, 138, 4).with_alpha(0.5),
+        error_background: Rgba::rgb(254, 226, 226).with_alpha(0.9),
+        error_border: Rgba::rgb(220, 38, 38).with_alpha(0.5),
+        checked: SeatColors {
+            background: Rgba::rgb(34, 197, 94).with_alpha(0.45),
+            border: Rgba::rgb(34, 197, 94).with_alpha(0.55),
+            foreground: Rgba::rgb(5, 46, 22),
+        },
+        unchecked: SeatColors {
+            background: Rgba::rgb(239, 68, 68).with_alpha(0.45),
+            border: Rgba::rgb(239, 68, 68).with_alpha(0.55),
+            foreground: Rgba::rgb(69, 16, 16),
+        },
+        marked: SeatColors {
+            background: Rgba::rgb(250, 204, 21).with_alpha(0.45),
+            border: Rgba::rgb(250, 204, 21).with_alpha(0.55),
+            foreground: Rgba::rgb(66, 32, 6),
+        },
+        blocked: SeatColors {
+            background: Rgba::rgb(71, 85, 105),
+            border: Rgba::rgb(51, 65, 75),
+            foreground: Rgba::rgb(203, 213, 225),
+        },
+        edit_pending: SeatColors {
+            background: Rgba::rgb(226, 232, 240),
+            border: Rgba::rgb(148, 163, 184),
+            foreground: Rgba::rgb(51, 65, 85),
+        },
+        selected: Rgba::rgb(14, 165, 233),
+        highlighted: Rgba::rgb(245, 158, 11),
+    };
+
+    pub const DARK: Self = Self {
+        dark: true,
+        window_background: Rgba::rgb(17, 24, 39),
+        panel_background: Rgba::rgb(30, 41, 59).with_alpha(0.85),
+        panel_border: Rgba::rgb(71, 85, 105).with_alpha(0.6),
+        text: Rgba::rgb(241, 245, 249),
+        secondary_text: Rgba::rgb(203, 213, 225),
+        muted_text: Rgba::rgb(148, 163, 184),
+        success_text: Rgba::rgb(74, 222, 128),
+        error_text: Rgba::rgb(248, 113, 113),
+        warning_background: Rgba::rgb(66, 32, 6).with_alpha(0.9),
+        warning_border: Rgba::rgb(234, 179, 8).with_alpha(0.6),
+        error_background: Rgba::rgb(69, 10, 10).with_alpha(0.9),
+        error_border: Rgba::rgb(248, 113, 113).with_alpha(0.6),
+        checked: SeatColors {
+            background: Rgba::rgb(22, 163, 74).with_alpha(0.55),
+            border: Rgba::rgb(74, 222, 128).with_alpha(0.6),
+            foreground: Rgba::rgb(220, 252, 231),
+        },
+        unchecked: SeatColors {
+            background: Rgba::rgb(220, 38, 38).with_alpha(0.5),
+            border: Rgba::rgb(248, 113, 113).with_alpha(0.6),
+            foreground: Rgba::rgb(254, 226, 226),
+        },
+        marked: SeatColors {
+            background: Rgba::rgb(202, 138, 4).with_alpha(0.55),
+            border: Rgba::rgb(250, 204, 21).with_alpha(0.6),
+            foreground: Rgba::rgb(254, 249, 195),
+        },
+        blocked: SeatColors {
+            background: Rgba::rgb(51, 65, 85),
+            border: Rgba::rgb(30, 41, 59),
+            foreground: Rgba::rgb(148, 163, 184),
+        },
+        edit_pending: SeatColors {
+            background: Rgba::rgb(30, 41, 59),
+            border: Rgba::rgb(71, 85, 105),
+            foreground: Rgba::rgb(226, 232, 240),
+        },
+        selected: Rgba::rgb(56, 189, 248),
+        highlighted: Rgba::rgb(251, 191, 36),
+    };
+
+    /// Black on white with statuses told apart by lightness, not just hue.
+    pub const HIGH_CONTRAST: Self = Self {
+        dark: false,
+        window_background: Rgba::rgb(255, 255, 255),
+        panel_background: Rgba::rgb(255, 255, 255),
+        panel_border: Rgba::rgb(0, 0, 0),
+        text: Rgba::rgb(0, 0, 0),
+        secondary_text: Rgba::rgb(0, 0, 0),
+        muted_text: Rgba::rgb(51, 51, 51),
+        success_text: Rgba::rgb(0, 100, 0),
+        error_text: Rgba::rgb(160, 0, 0),
+        warning_background: Rgba::rgb(255, 255, 160),
+        warning_border: Rgba::rgb(0, 0, 0),
+        error_background: Rgba::rgb(255, 200, 200),
+        error_border: Rgba::rgb(0, 0, 0),
+        checked: SeatColors {
+            background: Rgba::rgb(0, 0, 0),
+            border: Rgba::rgb(0, 0, 0),
+            foreground: Rgba::rgb(255, 255, 255),
+        },
+        unchecked: SeatColors {
+            background: Rgba::rgb(255, 255, 255),
+            border: Rgba::rgb(0, 0, 0),
+            foreground: Rgba::rgb(0, 0, 0),
+        },
+        marked: SeatColors {
+            background: Rgba::rgb(255, 221, 0),
+            border: Rgba::rgb(0, 0, 0),
+            foreground: Rgba::rgb(0, 0, 0),
+        },
+        blocked: SeatColors {
+            background: Rgba::rgb(96, 96, 96),
+            border: Rgba::rgb(0, 0, 0),
+            foreground: Rgba::rgb(255, 255, 255),
+        },
+        edit_pending: SeatColors {
+            background: Rgba::rgb(255, 255, 255),
+            border: Rgba::rgb(0, 0, 0),
+            foreground: Rgba::rgb(0, 0, 0),
+        },
+        selected: Rgba::rgb(0, 0, 255),
+        highlighted: Rgba::rgb(255, 128, 0),
+    };
+
+    pub fn status(&self, status: AttendanceStatus) -> SeatColors {
+        match status {
+            AttendanceStatus::Checked => self.checked,
+            AttendanceStatus::Unchecked => self.unchecked,
+            AttendanceStatus::Marked => self.marked,
+        }
+    }
+
+    /// The theme for a choice. `system_dark` is the desktop's dark-mode preference,
+    /// followed by `System` and by custom themes without a `base`.
+    pub fn resolve(
+        choice: ThemeChoice,
+        system_dark: bool,
+        custom_file: &Path,
+    ) -> Result<Self, ConfigError> {
+        match choice {
+            ThemeChoice::Custom => Self::load_custom(custom_file, system_dark),
+            built_in => Ok(Self::built_in(built_in, system_dark)),
+        }
+    }
+
+    pub fn load_custom(path: &Path, system_dark: bool) -> Result<Self, ConfigError> {
+        Self::parse_custom(&fs::read_to_string(path)?, system_dark)
+    }
+
+    /// Applies the overrides in a custom theme file on top of its base theme.
+    pub fn parse_custom(text: &str, system_dark: bool) -> Result<Self, ConfigError> {
+        let mut overrides: serde_json::Map<String, Value> = serde_json::from_str(text)?;
+        let base = match overrides.remove("base") {
+            None => ThemeChoice::System,
+            Some(Value::String(name)) => name
+                .parse()
+                .ok()
+                .filter(|choice| *choice != ThemeChoice::Custom)
+                .ok_or_else(|| {
+                    serde_json::Error::custom(format!(
+                        "unknown base theme {name:?}, expected system, light, dark or high-contrast"
+                    ))
+                })?,
+            Some(other) => {
+                return Err(serde_json::Error::custom(format!(
+                    "base must be a theme name, found {other}"
+                ))
+                .into())
+            }
+        };
+
+        let mut theme = serde_json::to_value(Self::built_in(base, system_dark))?;
+        merge_json(&mut theme, Value::Object(overrides));
+        Ok(serde_json::from_value(theme)?)
+    }
+
+    /// A built-in theme; `Custom` stands for its default base here, like `System`.
+    pub fn built_in(choice: ThemeChoice, system_dark: bool) -> Self {
+        match choice {
+            ThemeChoice::System | ThemeChoice::Custom if system_dark => Self::DARK,
+            ThemeChoice::System | ThemeChoice::Custom | ThemeChoice::Light => Self::LIGHT,
+            ThemeChoice::Dark => Self::DARK,
+            ThemeChoice::HighContrast => Self::HIGH_CONTRAST,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::LIGHT
+    }
+}
+
+/// Overwrites `target` with `overrides`, descending into objects present in both.
+fn merge_json(target: &mut Value, overrides: Value) {
+    match (target, overrides) {
+        (Value::Object(target), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                match target.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (target, overrides) => *target = overrides,
+    }
+}
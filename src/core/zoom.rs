@@ -0,0 +1,82 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use super::write_file_atomic;
+
+/// Smallest board scale, relative to the default cell size.
+pub const MIN_ZOOM: f64 = 0.25;
+/// Largest board scale, relative to the default cell size.
+pub const MAX_ZOOM: f64 = 4.0;
+/// Factor applied by one zoom step.
+pub const ZOOM_STEP: f64 = 1.25;
+
+/// How the seating board is sized.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Zoom {
+    /// Stretches the board to the window.
+    #[default]
+    Fit,
+    /// Fixed scale of the default cell size, scrolled when larger than the window.
+    Scale(f64),
+}
+
+impl Zoom {
+    /// A fixed scale, clamped to `MIN_ZOOM..=MAX_ZOOM`.
+    pub fn scale(scale: f64) -> Self {
+        if scale.is_finite() {
+            Self::Scale(scale.clamp(MIN_ZOOM, MAX_ZOOM))
+        } else {
+            Self::Fit
+        }
+    }
+
+    /// Multiplies the scale by `factor`, starting from `current`, the scale the board
+    /// is drawn at right now. In `Fit` that depends on the window size.
+    pub fn scaled_by(current: f64, factor: f64) -> Self {
+        Self::scale(current * factor)
+    }
+
+    pub fn zoomed_in(current: f64) -> Self {
+        Self::scaled_by(current, ZOOM_STEP)
+    }
+
+    pub fn zoomed_out(current: f64) -> Self {
+        Self::scaled_by(current, 1.0 / ZOOM_STEP)
+    }
+
+    /// Reads a remembered zoom level; missing or unreadable files mean `Fit`.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|text| text.parse().ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(self, path: &Path) -> io::Result<()> {
+        write_file_atomic(path, self.to_string().as_bytes())
+    }
+}
+
+impl fmt::Display for Zoom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fit => write!(f, "fit"),
+            Self::Scale(scale) => write!(f, "{scale:.3}"),
+        }
+    }
+}
+
+impl FromStr for Zoom {
+    type Err = std::num::ParseFloatError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("fit") {
+            return Ok(Self::Fit);
+        }
+        value.parse().map(Self::scale)
+    }
+}
@@ -3,7 +3,7 @@ pub mod ui;
 pub mod utilities;
 
 pub use core::{
-    AppMode, AttendanceBook, AttendanceStatistics, AttendanceStatus, CellKind, Configuration,
-    Position, Subject, Table,
+    AppMode, AttendanceBook, AttendanceStatistics, AttendanceStatus, CellKind, ConfigError,
+    Configuration, Position, Subject, Table,
 };
 pub use ui::{AppView, ModeSwitch, StatisticsPanel, StatusDialog, TableView};
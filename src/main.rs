@@ -1,12 +1,35 @@
 use std::env;
-use std::path::PathBuf;
+use std::process::{ExitCode, Termination};
 
+use checkin::core::{
+    validate_config_file, CliCommand, ConfigDiagnostic, SettingsError, SettingsLayer, USAGE,
+};
 use checkin::{AppView, Configuration, Table};
 use gtk4::prelude::*;
 use gtk4::{Application, ApplicationWindow};
 
-fn main() {
-    let configuration = parse_configuration(env::args().skip(1).collect());
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (validate, cli) = match CliCommand::parse(&args) {
+        Ok(CliCommand::Help) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Ok(CliCommand::Run(cli)) => (false, cli),
+        Ok(CliCommand::Validate(cli)) => (true, cli),
+        Err(error) => return usage_error(&error),
+    };
+    let configuration = match SettingsLayer::from_env(env::vars())
+        .and_then(|env| Configuration::resolve(cli, env))
+    {
+        Ok(configuration) => configuration,
+        Err(error) => return usage_error(&error),
+    };
+    if validate {
+        return run_validate(&configuration);
+    }
+
+    prepare_directories(&configuration);
     let app = Application::builder()
         .application_id("io.github.andeibuite.checkin")
         .build();
@@ -19,44 +42,86 @@ fn main() {
             .default_height(620)
             .build();
 
-        let table = if configuration.config_file.exists() {
-            Table::load_config(&configuration.config_file).unwrap_or_else(|error| {
-                eprintln!(
-                    "failed to load config {}: {error}, using default table",
-                    configuration.config_file.display()
-                );
-                Table::default_table()
-            })
-        } else {
-            Table::default_table()
+        let (table, diagnostics, load_failed) = match load_table(&configuration) {
+            Ok((table, diagnostics)) => (table, diagnostics, false),
+            Err(diagnostic) => (
+                configuration.default_layout.build_table(),
+                vec![diagnostic],
+                true,
+            ),
         };
         let app_view = AppView::new(&table, configuration.clone());
+        app_view.show_config_diagnostics(&diagnostics);
+        if load_failed {
+            app_view.offer_backup_restore();
+        }
         window.set_child(Some(app_view.widget()));
         window.present();
     });
 
-    app.run();
+    // Options were handled above; GTK would reject them as unknown.
+    let program = env::args()
+        .next()
+        .unwrap_or_else(|| String::from("checkin"));
+    app.run_with_args(&[program]).report()
 }
 
-fn parse_configuration(args: Vec<String>) -> Configuration {
-    let mut config_file = Configuration::default_config_file();
-    let mut index = 0usize;
-    while index < args.len() {
-        let arg = &args[index];
-        if let Some(path) = arg.strip_prefix("--config=") {
-            config_file = PathBuf::from(path);
-            index += 1;
-            continue;
+fn usage_error(error: &SettingsError) -> ExitCode {
+    eprintln!("checkin: {error}");
+    ExitCode::from(2)
+}
+
+/// Creates per-user directories and picks up a config left next to the binary.
+fn prepare_directories(configuration: &Configuration) {
+    if let Err(error) = configuration.ensure_dirs() {
+        eprintln!("failed to create checkin directories: {error}");
+    }
+    match configuration.migrate_legacy_config_file() {
+        Ok(Some(legacy)) => eprintln!(
+            "copied legacy config {} to {}",
+            legacy.display(),
+            configuration.config_file.display()
+        ),
+        Ok(None) => {}
+        Err(error) => eprintln!("failed to migrate legacy config: {error}"),
+    }
+}
+
+/// Loads the configured table together with its validation findings.
+///
+/// A missing file yields the default layout; a broken one is reported as a diagnostic.
+fn load_table(
+    configuration: &Configuration,
+) -> Result<(Table, Vec<ConfigDiagnostic>), ConfigDiagnostic> {
+    if !configuration.config_file.exists() {
+        return Ok((configuration.default_layout.build_table(), Vec::new()));
+    }
+
+    match Table::load_config(&configuration.config_file) {
+        Ok(table) => Ok((table, validate_config_file(&configuration.config_file))),
+        Err(error) => {
+            let mut diagnostic = ConfigDiagnostic::from_error(&error);
+            diagnostic.message.push_str(", using default table");
+            eprintln!("{}: {diagnostic}", configuration.config_file.display());
+            Err(diagnostic)
         }
-        if arg == "--config" {
-            if let Some(path) = args.get(index + 1) {
-                config_file = PathBuf::from(path);
-                index += 2;
-                continue;
-            }
-            eprintln!("--config requires a file path, falling back to default");
+    }
+}
+
+/// `checkin validate [--config <path>]`: prints diagnostics and fails on errors.
+fn run_validate(configuration: &Configuration) -> ExitCode {
+    let config_file = &configuration.config_file;
+    let diagnostics = validate_config_file(config_file);
+    for diagnostic in &diagnostics {
+        println!("{}: {diagnostic}", config_file.display());
+    }
+
+    if diagnostics.iter().any(ConfigDiagnostic::is_error) {
+        ExitCode::FAILURE
+    } else {
+        if diagnostics.is_empty() {
+            println!("{}: ok", config_file.display());
         }
-        index += 1;
+        ExitCode::SUCCESS
     }
-    Configuration::new(config_file)
 }
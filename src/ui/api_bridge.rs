@@ -0,0 +1,86 @@
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, TcpListener};
+use std::rc::Rc;
+use std::sync::mpsc::{self, Sender};
+use std::time::Duration;
+
+use futures_channel::mpsc as async_mpsc;
+use gtk4::glib;
+
+use crate::core::{ApiAccess, ApiRequest, ApiResponse};
+use crate::ui::table_view::TableView;
+use crate::utilities::{serve, HttpRequest, HttpResponse};
+
+const REPLY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Serves the REST API on a background thread and applies requests on the GTK main loop.
+pub struct ApiBridge;
+
+impl ApiBridge {
+    /// Binds `127.0.0.1:port` and starts serving requests that pass `access`.
+    /// Returns the bound address.
+    pub fn start(
+        port: u16,
+        access: ApiAccess,
+        table_view: Rc<TableView>,
+    ) -> io::Result<SocketAddr> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        let address = listener.local_addr()?;
+        serve_on_main_loop(
+            listener,
+            move |request| {
+                access
+                    .check(&request.method, &request.headers)
+                    .and_then(|()| {
+                        ApiRequest::route(&request.method, &request.path, &request.body_text())
+                    })
+                    .map_err(|response| Self::to_http(&response))
+            },
+            move |api_request| Self::to_http(&table_view.handle_api_request(&api_request)),
+        );
+        Ok(address)
+    }
+
+    fn to_http(response: &ApiResponse) -> HttpResponse {
+        HttpResponse::json(response.status, &response.body)
+    }
+}
+
+/// Serves `listener`, answering each request with `handle` on the GTK main loop.
+///
+/// Table state lives in GTK-owned `Rc`s, so server threads parse requests with `route`
+/// and hand them over a channel, then wait for the main loop to answer. The main loop
+/// only wakes when a request arrives. An `Err` from `route` is sent back as-is without
+/// involving the main loop.
+pub(crate) fn serve_on_main_loop<R, Route, Handle>(
+    listener: TcpListener,
+    route: Route,
+    handle: Handle,
+) where
+    R: Send + 'static,
+    Route: Fn(&HttpRequest) -> Result<R, HttpResponse> + Send + Sync + 'static,
+    Handle: Fn(R) -> HttpResponse + 'static,
+{
+    let (sender, mut receiver) = async_mpsc::unbounded::<(R, Sender<HttpResponse>)>();
+
+    serve(listener, move |request| {
+        let routed = match route(&request) {
+            Ok(routed) => routed,
+            Err(response) => return response,
+        };
+
+        let (reply_sender, reply_receiver) = mpsc::channel();
+        let response = if sender.unbounded_send((routed, reply_sender)).is_ok() {
+            reply_receiver.recv_timeout(REPLY_TIMEOUT).ok()
+        } else {
+            None
+        };
+        response.unwrap_or_else(|| HttpResponse::text(503, "app is not responding"))
+    });
+
+    glib::spawn_future_local(async move {
+        while let Ok((routed, reply)) = receiver.recv().await {
+            let _ = reply.send(handle(routed));
+        }
+    });
+}
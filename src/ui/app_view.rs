@@ -1,22 +1,52 @@
+use std::cell::{Cell, RefCell};
+use std::io;
+use std::path::Path;
 use std::rc::Rc;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 use gtk4::prelude::*;
-use gtk4::{Box as GtkBox, Button, Label, Orientation};
+use gtk4::{gdk, glib, Box as GtkBox, Button, DropDown, EventControllerKey, Label, Orientation};
 
-use crate::core::{AppMode, Configuration, Table};
+use crate::core::{
+    recent_completion, validate_config_file, write_private_file_atomic, ApiAccess, AppMode,
+    ConfigBackups, ConfigDiagnostic, ConfigError, ConfigSync, Configuration, ExternalChange,
+    Roster, SessionRecord, SessionStore, Table, Theme, ThemeChoice, Zoom, API_TOKEN_HEADER,
+};
+use crate::ui::api_bridge::ApiBridge;
+use crate::ui::attendance_history_dialog::AttendanceHistoryDialog;
+use crate::ui::config_watcher::ConfigWatcher;
+use crate::ui::confirm_dialog::ConfirmDialog;
+use crate::ui::diagnostics_banner::DiagnosticsBanner;
 use crate::ui::mode_switch::ModeSwitch;
+use crate::ui::picker_panel::PickerPanel;
+use crate::ui::qr_code_view::QrCodeView;
+use crate::ui::roll_call_dialog::RollCallDialog;
+use crate::ui::scan_panel::ScanPanel;
+use crate::ui::self_checkin_panel::SelfCheckInPanel;
 use crate::ui::statistics_panel::StatisticsPanel;
+use crate::ui::system_appearance::SystemAppearance;
 use crate::ui::table_view::TableView;
-use crate::ui::ui_styles::ensure_ui_styles;
-use crate::utilities::write_text_to_clipboard;
+use crate::ui::ui_styles::{apply_theme, ensure_ui_styles};
+use crate::utilities::{read_text_from_clipboard, write_text_to_clipboard};
+
+/// Stored sessions shown in the statistics trend chart.
+const TREND_SESSION_COUNT: usize = 10;
+/// Quiet time after the last zoom change before the level is written, so a scroll or
+/// pinch gesture saves once rather than on every step.
+const ZOOM_SAVE_DELAY: Duration = Duration::from_millis(500);
 
 /// Top-level app content that composes all UI components.
 pub struct AppView {
     root: GtkBox,
-    _configuration: Configuration,
+    configuration: Configuration,
+    diagnostics_banner: DiagnosticsBanner,
+    config_sync: Rc<RefCell<ConfigSync>>,
     _mode_switch: ModeSwitch,
-    _table_view: Rc<TableView>,
+    _self_checkin_panel: SelfCheckInPanel,
+    _scan_panel: ScanPanel,
+    _picker_panel: PickerPanel,
+    _system_appearance: SystemAppearance,
+    table_view: Rc<TableView>,
     _statistics_panel: StatisticsPanel,
     pub stats_label: Label,
 }
@@ -33,52 +63,315 @@ impl AppView {
         root.set_margin_start(20);
         root.set_margin_end(20);
 
+        let diagnostics_banner = DiagnosticsBanner::new();
+        let system_appearance = SystemAppearance::new();
+        Self::apply_configured_theme(
+            &configuration,
+            system_appearance.prefers_dark(),
+            &diagnostics_banner,
+        );
+        if matches!(
+            configuration.theme,
+            ThemeChoice::System | ThemeChoice::Custom
+        ) {
+            let configuration = configuration.clone();
+            let banner = diagnostics_banner.clone();
+            system_appearance.connect_changed(move |dark| {
+                Self::apply_configured_theme(&configuration, dark, &banner);
+            });
+        }
+        let config_sync = Rc::new(RefCell::new(ConfigSync::new(table)));
+
         let mode_switcher = GtkBox::new(Orientation::Horizontal, 12);
         mode_switcher.add_css_class("toolbar");
-        let mode_switch = ModeSwitch::new(AppMode::CheckIn);
+        let initial_mode = configuration.initial_mode;
+        let mode_switch = ModeSwitch::new(initial_mode);
         let copy_statistics_button = Button::with_label("copy statistics");
-        copy_statistics_button.set_visible(true);
+        copy_statistics_button.set_visible(initial_mode == AppMode::CheckIn);
+        let roll_call_button = Button::with_label("roll call");
+        roll_call_button.set_visible(initial_mode == AppMode::CheckIn);
+        let new_session_button = Button::with_label("new session");
+        new_session_button.set_visible(initial_mode == AppMode::CheckIn);
+        let save_session_button = Button::with_label("save session");
+        save_session_button.set_visible(initial_mode == AppMode::CheckIn);
+        let history_button = Button::with_label("history");
+        let copy_grid_button = Button::with_label("copy grid");
+        copy_grid_button.set_visible(initial_mode == AppMode::Edit);
+        let paste_grid_button = Button::with_label("paste grid");
+        paste_grid_button.set_visible(initial_mode == AppMode::Edit);
+        let merge_cells_button = Button::with_label("merge cells");
+        merge_cells_button.set_visible(initial_mode == AppMode::Edit);
+        let split_cell_button = Button::with_label("split cell");
+        split_cell_button.set_visible(initial_mode == AppMode::Edit);
         mode_switcher.append(mode_switch.widget());
         mode_switcher.append(&copy_statistics_button);
+        mode_switcher.append(&roll_call_button);
+        mode_switcher.append(&new_session_button);
+        mode_switcher.append(&save_session_button);
+        mode_switcher.append(&history_button);
+        mode_switcher.append(&copy_grid_button);
+        mode_switcher.append(&paste_grid_button);
+        mode_switcher.append(&merge_cells_button);
+        mode_switcher.append(&split_cell_button);
 
         let board_shell = GtkBox::new(Orientation::Vertical, 0);
         board_shell.add_css_class("board-shell");
         board_shell.set_vexpand(true);
 
         let table_view = Rc::new(TableView::new(table));
+        table_view.set_board(configuration.board);
+        table_view.set_mode(initial_mode);
+        table_view.set_zoom(Zoom::load(&configuration.zoom_file()));
+        {
+            let zoom_file = configuration.zoom_file();
+            let pending_save: Rc<RefCell<Option<glib::SourceId>>> = Rc::new(RefCell::new(None));
+            table_view.connect_zoom_changed(move |zoom| {
+                if let Some(source) = pending_save.borrow_mut().take() {
+                    source.remove();
+                }
+                let zoom_file = zoom_file.clone();
+                let pending = Rc::clone(&pending_save);
+                let source = glib::timeout_add_local_once(ZOOM_SAVE_DELAY, move || {
+                    pending.borrow_mut().take();
+                    if let Err(error) = zoom.save(&zoom_file) {
+                        eprintln!(
+                            "failed to remember zoom in {}: {error}",
+                            zoom_file.display()
+                        );
+                    }
+                });
+                pending_save.borrow_mut().replace(source);
+            });
+        }
+        mode_switcher.append(&Self::build_zoom_controls(&table_view));
         let statistics_panel = StatisticsPanel::new(table_view.get_statistics());
         let stats_label = statistics_panel.summary_label();
+        Self::refresh_session_trend(&statistics_panel, &configuration.session_store());
+        let roster = Self::load_roster(&configuration, &diagnostics_banner);
+        let self_checkin_panel = SelfCheckInPanel::new(
+            configuration.self_checkin_port,
+            roster.clone(),
+            Rc::clone(&table_view),
+        );
+        mode_switcher.append(self_checkin_panel.widget());
+        let qr_code_view = QrCodeView::new();
+        {
+            let qr_code_view = qr_code_view.clone();
+            self_checkin_panel.connect_link_changed(move |link| {
+                qr_code_view.set_text(link.as_deref());
+            });
+        }
+        let scan_panel = ScanPanel::new(roster, Rc::clone(&table_view));
+        scan_panel.set_available(initial_mode == AppMode::CheckIn);
+        scan_panel.attach_keys(&root);
+        mode_switcher.append(scan_panel.widget());
+        let picker_panel = PickerPanel::new(Rc::clone(&table_view));
+        picker_panel
+            .widget()
+            .set_visible(initial_mode == AppMode::CheckIn);
+        mode_switcher.append(picker_panel.widget());
 
+        statistics_panel.set_group_breakdown(&table_view.group_statistics());
         {
             let panel_for_updates = statistics_panel.clone();
+            let table_view_for_updates = Rc::clone(&table_view);
             table_view.connect_status_changed(move |statistics| {
                 panel_for_updates.update(statistics);
+                panel_for_updates.set_group_breakdown(&table_view_for_updates.group_statistics());
             });
         }
         {
             let config_file = configuration.config_file.clone();
+            let backups = configuration.backups();
+            let config_sync = Rc::clone(&config_sync);
+            let banner = diagnostics_banner.clone();
+            let weak_table_view = Rc::downgrade(&table_view);
+            // Leaving edit mode exports the table even when nothing was edited; only
+            // real edits are written, so mode toggles don't rotate backups away.
             table_view.connect_table_exported(move |table| {
-                if let Err(error) = table.write_config(&config_file) {
+                if !config_sync.borrow().is_dirty(&table) {
+                    return;
+                }
+                // A file edited elsewhere meanwhile goes through the reload prompt
+                // instead of being overwritten.
+                match Self::check_config_file(&config_file, &config_sync, &table) {
+                    Ok(ExternalChange::Unchanged) => {}
+                    Err(ConfigError::Io(error)) if error.kind() == io::ErrorKind::NotFound => {}
+                    Ok(change) => {
+                        if let Some(table_view) = weak_table_view.upgrade() {
+                            Self::offer_reload(
+                                &banner,
+                                &table_view,
+                                &config_sync,
+                                &config_file,
+                                &backups,
+                                change,
+                            );
+                        }
+                        return;
+                    }
+                    Err(error) => {
+                        let source = config_file.display().to_string();
+                        banner.show(&source, &[ConfigDiagnostic::from_error(&error)]);
+                        return;
+                    }
+                }
+                if !config_sync.borrow().is_dirty(&table) {
+                    return;
+                }
+                if let Err(error) = table.write_config_with_backup(&config_file, &backups) {
                     eprintln!(
                         "failed to write table config to {}: {error}",
                         config_file.display()
                     );
+                    return;
                 }
+                config_sync.borrow_mut().mark_synced(&table);
             });
         }
         {
             let table_view = Rc::clone(&table_view);
             let copy_statistics_button = copy_statistics_button.clone();
+            let copy_grid_button = copy_grid_button.clone();
+            let paste_grid_button = paste_grid_button.clone();
+            let merge_cells_button = merge_cells_button.clone();
+            let split_cell_button = split_cell_button.clone();
+            let roll_call_button = roll_call_button.clone();
+            let new_session_button = new_session_button.clone();
+            let save_session_button = save_session_button.clone();
+            let scan_panel = scan_panel.clone();
+            let picker_widget = picker_panel.widget().clone();
             mode_switch.connect_toggled(move |mode| {
                 table_view.set_mode(mode);
+                scan_panel.set_available(mode == AppMode::CheckIn);
                 copy_statistics_button.set_visible(mode == AppMode::CheckIn);
+                roll_call_button.set_visible(mode == AppMode::CheckIn);
+                new_session_button.set_visible(mode == AppMode::CheckIn);
+                save_session_button.set_visible(mode == AppMode::CheckIn);
+                picker_widget.set_visible(mode == AppMode::CheckIn);
+                copy_grid_button.set_visible(mode == AppMode::Edit);
+                paste_grid_button.set_visible(mode == AppMode::Edit);
+                merge_cells_button.set_visible(mode == AppMode::Edit);
+                split_cell_button.set_visible(mode == AppMode::Edit);
+            });
+        }
+        // A session runs from app start or the last "new session" click; its start names
+        // the saved file and is what late check-ins are measured from.
+        let session_started_at = Rc::new(Cell::new(SystemTime::now()));
+        let save_session: Rc<dyn Fn() -> bool> = {
+            let table_view = Rc::clone(&table_view);
+            let session_started_at = Rc::clone(&session_started_at);
+            let store = configuration.session_store();
+            let late_after = configuration.late_after;
+            let banner = diagnostics_banner.clone();
+            let statistics_panel = statistics_panel.clone();
+            Rc::new(move || {
+                Self::save_session(
+                    &table_view,
+                    session_started_at.get(),
+                    late_after,
+                    &store,
+                    &banner,
+                    &statistics_panel,
+                )
+            })
+        };
+        {
+            let table_view = Rc::clone(&table_view);
+            let session_started_at = Rc::clone(&session_started_at);
+            let save_session = Rc::clone(&save_session);
+            let banner = diagnostics_banner.clone();
+            new_session_button.connect_clicked(move |button| {
+                let table_view = Rc::clone(&table_view);
+                let session_started_at = Rc::clone(&session_started_at);
+                let save_session = Rc::clone(&save_session);
+                let banner = banner.clone();
+                ConfirmDialog::present(
+                    button,
+                    "Start a new session?",
+                    "The current attendance is saved to history first, then every seat is \
+                     reset and late check-ins count from now.",
+                    "save and start new",
+                    move || {
+                        // Nothing marked yet means there is nothing worth keeping.
+                        let has_attendance = table_view.get_statistics().completed_count() > 0;
+                        if has_attendance && !save_session() {
+                            return;
+                        }
+                        session_started_at.set(SystemTime::now());
+                        table_view.reset_attendance();
+                        banner.show_message(
+                            "New session started",
+                            "the previous session is in the history",
+                            false,
+                        );
+                    },
+                );
+            });
+        }
+        save_session_button.connect_clicked(move |_| {
+            save_session();
+        });
+        {
+            let store = configuration.session_store();
+            history_button.connect_clicked(move |button| {
+                AttendanceHistoryDialog::present(button, &store);
+            });
+        }
+        {
+            let table_view = Rc::clone(&table_view);
+            roll_call_button.connect_clicked(move |button| {
+                RollCallDialog::present(button, Rc::clone(&table_view));
+            });
+        }
+        {
+            let table_view = Rc::clone(&table_view);
+            copy_grid_button.connect_clicked(move |_| {
+                Self::copy_grid(&table_view);
             });
         }
         {
             let table_view = Rc::clone(&table_view);
+            paste_grid_button.connect_clicked(move |_| {
+                Self::paste_grid(&table_view);
+            });
+        }
+        {
+            let table_view = Rc::clone(&table_view);
+            merge_cells_button.connect_clicked(move |_| {
+                table_view.merge_selection();
+            });
+        }
+        {
+            let table_view = Rc::clone(&table_view);
+            split_cell_button.connect_clicked(move |_| {
+                table_view.split_selection();
+            });
+        }
+        {
+            let table_view = Rc::clone(&table_view);
+            let keys = EventControllerKey::new();
+            keys.connect_key_pressed(move |_, key, _, modifiers| {
+                if table_view.mode() != AppMode::Edit
+                    || !modifiers.contains(gdk::ModifierType::CONTROL_MASK)
+                {
+                    return glib::Propagation::Proceed;
+                }
+                match key.to_lower() {
+                    gdk::Key::c => Self::copy_grid(&table_view),
+                    gdk::Key::v => Self::paste_grid(&table_view),
+                    _ => return glib::Propagation::Proceed,
+                }
+                glib::Propagation::Stop
+            });
+            root.add_controller(keys);
+        }
+        {
+            let table_view = Rc::clone(&table_view);
+            let template = configuration.export_template().to_owned();
             copy_statistics_button.connect_clicked(move |_| {
                 let time: SystemTime = SystemTime::now();
-                let text = table_view.build_statistics_export_text_zh(&time);
+                let text = table_view.build_statistics_export_text(&time, &template);
                 if let Err(error) = write_text_to_clipboard(&text) {
                     eprintln!("copy statistics failed: {error}");
                 }
@@ -86,22 +379,420 @@ impl AppView {
         }
 
         board_shell.append(table_view.widget());
-        root.append(statistics_panel.widget());
+        root.append(diagnostics_banner.widget());
+        let statistics_row = GtkBox::new(Orientation::Horizontal, 12);
+        statistics_panel.widget().set_hexpand(true);
+        statistics_row.append(statistics_panel.widget());
+        statistics_row.append(qr_code_view.widget());
+        root.append(&statistics_row);
         root.append(&mode_switcher);
         root.append(&board_shell);
 
-        Self {
+        let view = Self {
             root,
-            _configuration: configuration,
+            configuration,
+            diagnostics_banner,
+            config_sync,
             _mode_switch: mode_switch,
-            _table_view: table_view,
+            _self_checkin_panel: self_checkin_panel,
+            _scan_panel: scan_panel,
+            _picker_panel: picker_panel,
+            _system_appearance: system_appearance,
+            table_view,
             _statistics_panel: statistics_panel,
             stats_label,
-        }
+        };
+        view.watch_config_file();
+        view.start_autosave();
+        view.start_api_server();
+        view
     }
 
     /// Returns the top-level root widget.
     pub fn widget(&self) -> &GtkBox {
         &self.root
     }
+
+    /// Shows config load and validation findings above the statistics panel.
+    pub fn show_config_diagnostics(&self, diagnostics: &[ConfigDiagnostic]) {
+        let source = self.configuration.config_file.display().to_string();
+        self.diagnostics_banner.show(&source, diagnostics);
+    }
+
+    /// Adds a backup picker to the diagnostics banner when backups of the config exist.
+    ///
+    /// Meant for when the main config failed to load and a default layout is shown instead.
+    pub fn offer_backup_restore(&self) {
+        let config_file = self.configuration.config_file.clone();
+        let backups = self.configuration.backups();
+        let candidates = match backups.list(&config_file) {
+            Ok(candidates) if !candidates.is_empty() => candidates,
+            Ok(_) => return,
+            Err(error) => {
+                eprintln!("failed to list config backups: {error}");
+                return;
+            }
+        };
+
+        let names: Vec<String> = candidates
+            .iter()
+            .map(|path| {
+                path.file_name()
+                    .map_or_else(String::new, |name| name.to_string_lossy().into_owned())
+            })
+            .collect();
+        let name_refs: Vec<&str> = names.iter().map(String::as_str).collect();
+
+        let picker = GtkBox::new(Orientation::Horizontal, 6);
+        let dropdown = DropDown::from_strings(&name_refs);
+        let restore_button = Button::with_label("restore backup");
+        picker.append(&dropdown);
+        picker.append(&restore_button);
+
+        {
+            let banner = self.diagnostics_banner.clone();
+            let table_view = Rc::clone(&self.table_view);
+            let config_sync = Rc::clone(&self.config_sync);
+            let source = config_file.display().to_string();
+            restore_button.connect_clicked(move |_| {
+                let Some(backup) = candidates.get(dropdown.selected() as usize) else {
+                    return;
+                };
+                let kept = match backups.restore(backup, &config_file) {
+                    Ok(kept) => kept,
+                    Err(error) => {
+                        let diagnostic = ConfigDiagnostic::from_error(&ConfigError::Io(error));
+                        banner.show(&source, &[diagnostic]);
+                        return;
+                    }
+                };
+
+                match Table::load_config(&config_file) {
+                    Ok(table) => {
+                        table_view.replace_table(&table);
+                        config_sync.borrow_mut().mark_synced(&table);
+                        banner.set_actions(None);
+                        let diagnostics = validate_config_file(&config_file);
+                        match kept {
+                            Some(kept) if diagnostics.is_empty() => banner.show_message(
+                                "Backup restored",
+                                &format!("the replaced file was kept as {}", kept.display()),
+                                false,
+                            ),
+                            _ => banner.show(&source, &diagnostics),
+                        }
+                    }
+                    Err(error) => {
+                        banner.show(&source, &[ConfigDiagnostic::from_error(&error)]);
+                    }
+                }
+            });
+        }
+
+        self.diagnostics_banner
+            .set_actions(Some(picker.upcast_ref()));
+    }
+
+    /// Periodically writes unsaved layout edits, per `Configuration::autosave_interval`,
+    /// as long as the file still holds the layout last synced.
+    fn start_autosave(&self) {
+        let interval = self.configuration.autosave_interval;
+        if interval.is_zero() {
+            return;
+        }
+
+        let config_file = self.configuration.config_file.clone();
+        let table_view = Rc::clone(&self.table_view);
+        let config_sync = Rc::clone(&self.config_sync);
+        glib::timeout_add_local(interval, move || {
+            let table = table_view.table();
+            if !config_sync.borrow().is_dirty(&table) {
+                return glib::ControlFlow::Continue;
+            }
+
+            // Only replace the layout last synced; external edits are left to the
+            // reload prompt of the file watcher.
+            let safe_to_write = match Self::check_config_file(&config_file, &config_sync, &table) {
+                Ok(change) => change == ExternalChange::Unchanged,
+                Err(ConfigError::Io(error)) => error.kind() == io::ErrorKind::NotFound,
+                Err(_) => false,
+            };
+            // Autosaves skip the backup rotation, which would soon hold only
+            // near-identical copies.
+            if safe_to_write && config_sync.borrow().is_dirty(&table) {
+                match table.write_config(&config_file) {
+                    Ok(()) => config_sync.borrow_mut().mark_synced(&table),
+                    Err(error) => {
+                        eprintln!("autosave to {} failed: {error}", config_file.display())
+                    }
+                }
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
+    /// Stores the running session in `store` and reports the outcome on `banner`.
+    /// Returns whether it was saved.
+    fn save_session(
+        table_view: &TableView,
+        started_at: SystemTime,
+        late_after: Option<Duration>,
+        store: &SessionStore,
+        banner: &DiagnosticsBanner,
+        statistics_panel: &StatisticsPanel,
+    ) -> bool {
+        let record = SessionRecord::capture(
+            &table_view.table(),
+            &table_view.attendance(),
+            started_at,
+            late_after,
+        );
+        match store.save(&record) {
+            Ok(path) => {
+                banner.show_message("Session saved", &path.display().to_string(), false);
+                Self::refresh_session_trend(statistics_panel, store);
+                true
+            }
+            Err(error) => {
+                banner.show_message(
+                    "Session not saved",
+                    &format!("{}: {error}", store.dir().display()),
+                    true,
+                );
+                false
+            }
+        }
+    }
+
+    fn refresh_session_trend(panel: &StatisticsPanel, store: &SessionStore) {
+        match store.load_all() {
+            Ok(records) => {
+                panel.set_session_trend(recent_completion(&records, TREND_SESSION_COUNT))
+            }
+            Err(error) => eprintln!(
+                "failed to read sessions from {}: {error}",
+                store.dir().display()
+            ),
+        }
+    }
+
+    /// Applies `Configuration::theme`. A custom theme that fails to load is reported,
+    /// and the built-in theme for the system preference is used instead.
+    fn apply_configured_theme(
+        configuration: &Configuration,
+        system_dark: bool,
+        banner: &DiagnosticsBanner,
+    ) {
+        let theme_file = configuration.theme_file();
+        let theme =
+            Theme::resolve(configuration.theme, system_dark, &theme_file).unwrap_or_else(|error| {
+                banner.show_message(
+                    "Theme not loaded",
+                    &format!("{}: {error}", theme_file.display()),
+                    true,
+                );
+                Theme::built_in(ThemeChoice::System, system_dark)
+            });
+        apply_theme(theme);
+    }
+
+    /// Reads the ID roster for card scans and self check-in; problems are reported and
+    /// leave it empty.
+    fn load_roster(configuration: &Configuration, banner: &DiagnosticsBanner) -> Roster {
+        let Some(roster_file) = &configuration.roster_file else {
+            return Roster::default();
+        };
+
+        Roster::load(roster_file).unwrap_or_else(|error| {
+            banner.show_message(
+                "Roster not loaded",
+                &format!("{}: {error}", roster_file.display()),
+                true,
+            );
+            Roster::default()
+        })
+    }
+
+    /// Starts the local REST API when `Configuration::api_port` is set.
+    fn start_api_server(&self) {
+        let Some(port) = self.configuration.api_port else {
+            return;
+        };
+
+        let access = ApiAccess::new();
+        let token_file = self.configuration.api_token_file();
+        if let Err(error) = write_private_file_atomic(&token_file, access.token().as_bytes()) {
+            eprintln!(
+                "failed to write the API token to {}: {error}",
+                token_file.display()
+            );
+        }
+        let token = access.token().to_owned();
+        match ApiBridge::start(port, access, Rc::clone(&self.table_view)) {
+            Ok(address) => eprintln!(
+                "REST API listening on http://{address}, send the header \"{API_TOKEN_HEADER}: {token}\" (also in {})",
+                token_file.display()
+            ),
+            Err(error) => self.diagnostics_banner.show_message(
+                "REST API unavailable",
+                &format!("could not listen on 127.0.0.1:{port}: {error}"),
+                true,
+            ),
+        }
+    }
+
+    /// Watches the config file and offers to reload it after external edits.
+    fn watch_config_file(&self) {
+        let config_file = self.configuration.config_file.clone();
+        let backups = self.configuration.backups();
+        let banner = self.diagnostics_banner.clone();
+        let table_view = Rc::clone(&self.table_view);
+        let config_sync = Rc::clone(&self.config_sync);
+        let watched = ConfigWatcher::attach(&self.root, &self.configuration.config_file, {
+            let config_file = config_file.clone();
+            move || {
+                let table = table_view.table();
+                match Self::check_config_file(&config_file, &config_sync, &table) {
+                    Ok(change) => Self::offer_reload(
+                        &banner,
+                        &table_view,
+                        &config_sync,
+                        &config_file,
+                        &backups,
+                        change,
+                    ),
+                    Err(error) => {
+                        let source = config_file.display().to_string();
+                        banner.show(&source, &[ConfigDiagnostic::from_error(&error)]);
+                    }
+                }
+            }
+        });
+        if let Err(error) = watched {
+            eprintln!("failed to watch {}: {error}", config_file.display());
+        }
+    }
+
+    /// Reads the config file and compares it with `table` and the last synced layout.
+    fn check_config_file(
+        config_file: &Path,
+        config_sync: &RefCell<ConfigSync>,
+        table: &Table,
+    ) -> Result<ExternalChange, ConfigError> {
+        let disk = Table::load_config(config_file)?;
+        Ok(config_sync.borrow_mut().classify(table, disk))
+    }
+
+    /// Offers to reload a config file changed elsewhere and, when the layout was edited
+    /// here too, to keep the local layout instead.
+    fn offer_reload(
+        banner: &DiagnosticsBanner,
+        table_view: &Rc<TableView>,
+        config_sync: &Rc<RefCell<ConfigSync>>,
+        config_file: &Path,
+        backups: &ConfigBackups,
+        change: ExternalChange,
+    ) {
+        let source = config_file.display().to_string();
+        let (disk, is_conflict) = match change {
+            ExternalChange::Unchanged => return,
+            ExternalChange::Reloadable(disk) => (disk, false),
+            ExternalChange::Conflict(disk) => (disk, true),
+        };
+        let (title, detail) = if is_conflict {
+            (
+                format!("{source} changed on disk and in the app"),
+                "Reload discards the layout edits made here; keep mine overwrites the file.",
+            )
+        } else {
+            (
+                format!("{source} changed on disk"),
+                "Reload to use the new layout. Check-in progress is kept for seats that still exist.",
+            )
+        };
+
+        let actions = GtkBox::new(Orientation::Horizontal, 6);
+        let reload_button = Button::with_label("reload");
+        actions.append(&reload_button);
+        {
+            let banner = banner.clone();
+            let table_view = Rc::clone(table_view);
+            let config_sync = Rc::clone(config_sync);
+            reload_button.connect_clicked(move |_| {
+                table_view.replace_table(&disk);
+                config_sync.borrow_mut().mark_synced(&disk);
+                banner.dismiss();
+            });
+        }
+        if is_conflict {
+            let keep_button = Button::with_label("keep mine");
+            actions.append(&keep_button);
+            let banner = banner.clone();
+            let table_view = Rc::clone(table_view);
+            let config_sync = Rc::clone(config_sync);
+            let config_file = config_file.to_path_buf();
+            let backups = backups.clone();
+            keep_button.connect_clicked(move |_| {
+                let table = table_view.table();
+                if let Err(error) = table.write_config_with_backup(&config_file, &backups) {
+                    let diagnostic = ConfigDiagnostic::from_error(&ConfigError::Io(error));
+                    banner.show(&config_file.display().to_string(), &[diagnostic]);
+                    return;
+                }
+                config_sync.borrow_mut().mark_synced(&table);
+                banner.dismiss();
+            });
+        }
+
+        banner.show_message(&title, detail, is_conflict);
+        banner.set_actions(Some(actions.upcast_ref()));
+    }
+
+    fn build_zoom_controls(table_view: &Rc<TableView>) -> GtkBox {
+        let controls = GtkBox::new(Orientation::Horizontal, 2);
+        controls.add_css_class("linked");
+        let zoom_out_button = Button::with_label("−");
+        zoom_out_button.set_tooltip_text(Some("zoom out (Ctrl+scroll)"));
+        let zoom_in_button = Button::with_label("+");
+        zoom_in_button.set_tooltip_text(Some("zoom in (Ctrl+scroll)"));
+        let fit_button = Button::with_label("fit");
+        fit_button.set_tooltip_text(Some("fit the board to the window"));
+
+        {
+            let table_view = Rc::clone(table_view);
+            zoom_out_button.connect_clicked(move |_| table_view.zoom_out());
+        }
+        {
+            let table_view = Rc::clone(table_view);
+            zoom_in_button.connect_clicked(move |_| table_view.zoom_in());
+        }
+        {
+            let table_view = Rc::clone(table_view);
+            fit_button.connect_clicked(move |_| table_view.set_zoom(Zoom::Fit));
+        }
+
+        controls.append(&zoom_out_button);
+        controls.append(&zoom_in_button);
+        controls.append(&fit_button);
+        controls
+    }
+
+    fn copy_grid(table_view: &TableView) {
+        let Some(text) = table_view.selection_grid_text() else {
+            return;
+        };
+        if let Err(error) = write_text_to_clipboard(&text) {
+            eprintln!("copy grid failed: {error}");
+        }
+    }
+
+    fn paste_grid(table_view: &Rc<TableView>) {
+        let table_view = Rc::clone(table_view);
+        let result = read_text_from_clipboard(move |text| {
+            table_view.paste_grid_text(&text);
+        });
+        if let Err(error) = result {
+            eprintln!("paste grid failed: {error}");
+        }
+    }
 }
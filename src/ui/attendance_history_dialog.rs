@@ -0,0 +1,186 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use chrono::NaiveDate;
+use gtk4::prelude::*;
+use gtk4::{
+    glib, Align, Box as GtkBox, Button, Entry, Grid, Label, Orientation, PolicyType,
+    ScrolledWindow, Widget, Window,
+};
+
+use crate::core::{
+    person_attendance, PersonAttendance, PersonAttendanceColumn, SessionRecord, SessionStore,
+};
+
+const CLASS_HEADER: &str = "history-header";
+const CLASS_STATUS: &str = "history-status";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+struct HistoryState {
+    records: Vec<SessionRecord>,
+    people: Vec<PersonAttendance>,
+    column: PersonAttendanceColumn,
+    descending: bool,
+}
+
+/// Window listing per-person attendance over stored sessions.
+///
+/// Dates filter the sessions included; clicking a column header sorts by it, and
+/// clicking it again flips the direction.
+pub struct AttendanceHistoryDialog;
+
+impl AttendanceHistoryDialog {
+    pub fn present(parent: &impl IsA<Widget>, store: &SessionStore) {
+        let window = Self::build(parent);
+        let status_label = Label::new(None);
+        status_label.add_css_class(CLASS_STATUS);
+        status_label.set_xalign(0.0);
+
+        let records = store.load_all().unwrap_or_else(|error| {
+            status_label.set_text(&format!("cannot read {}: {error}", store.dir().display()));
+            Vec::new()
+        });
+        let state = Rc::new(RefCell::new(HistoryState {
+            people: person_attendance(&records, None, None),
+            records,
+            column: PersonAttendanceColumn::default(),
+            descending: false,
+        }));
+
+        let content = GtkBox::new(Orientation::Vertical, 10);
+        content.set_margin_top(14);
+        content.set_margin_bottom(14);
+        content.set_margin_start(14);
+        content.set_margin_end(14);
+
+        let range = GtkBox::new(Orientation::Horizontal, 8);
+        let from_entry = Entry::builder().placeholder_text("from YYYY-MM-DD").build();
+        let to_entry = Entry::builder().placeholder_text("to YYYY-MM-DD").build();
+        let apply_button = Button::with_label("apply");
+        range.append(&from_entry);
+        range.append(&to_entry);
+        range.append(&apply_button);
+
+        let grid = Grid::builder().column_spacing(16).row_spacing(4).build();
+        let scroller = ScrolledWindow::builder()
+            .hscrollbar_policy(PolicyType::Automatic)
+            .vscrollbar_policy(PolicyType::Automatic)
+            .vexpand(true)
+            .child(&grid)
+            .build();
+
+        content.append(&range);
+        content.append(&status_label);
+        content.append(&scroller);
+
+        {
+            let state = Rc::clone(&state);
+            let grid = grid.clone();
+            let status_label = status_label.clone();
+            let from_entry = from_entry.clone();
+            let to_entry = to_entry.clone();
+            apply_button.connect_clicked(move |_| {
+                let (Some(from), Some(to)) = (
+                    Self::parse_date(&from_entry.text()),
+                    Self::parse_date(&to_entry.text()),
+                ) else {
+                    status_label.set_text("dates must look like 2024-09-01, or be left empty");
+                    return;
+                };
+                {
+                    let mut state = state.borrow_mut();
+                    state.people = person_attendance(&state.records, from, to);
+                    let (column, descending) = (state.column, state.descending);
+                    column.sort(&mut state.people, descending);
+                }
+                status_label.set_text("");
+                Self::render(&grid, &state);
+            });
+        }
+
+        Self::render(&grid, &state);
+        window.set_child(Some(&content));
+        window.present();
+    }
+
+    /// `Some(None)` for an empty field, `None` for an invalid date.
+    fn parse_date(text: &str) -> Option<Option<NaiveDate>> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Some(None);
+        }
+        NaiveDate::parse_from_str(text, DATE_FORMAT).ok().map(Some)
+    }
+
+    fn render(grid: &Grid, state: &Rc<RefCell<HistoryState>>) {
+        while let Some(child) = grid.first_child() {
+            grid.remove(&child);
+        }
+
+        let state_ref = state.borrow();
+        for (column_index, column) in PersonAttendanceColumn::ALL.into_iter().enumerate() {
+            let arrow = match (state_ref.column == column, state_ref.descending) {
+                (true, false) => " ▲",
+                (true, true) => " ▼",
+                (false, _) => "",
+            };
+            let header = Button::with_label(&format!("{}{arrow}", column.label()));
+            header.add_css_class("flat");
+            header.add_css_class(CLASS_HEADER);
+            {
+                let state = Rc::clone(state);
+                let grid = grid.clone();
+                header.connect_clicked(move |_| {
+                    {
+                        let mut state = state.borrow_mut();
+                        state.descending = state.column == column && !state.descending;
+                        state.column = column;
+                        let descending = state.descending;
+                        column.sort(&mut state.people, descending);
+                    }
+                    // Rebuilding removes this button, so do it after the handler returns.
+                    let state = Rc::clone(&state);
+                    let grid = grid.clone();
+                    glib::idle_add_local_once(move || Self::render(&grid, &state));
+                });
+            }
+            grid.attach(&header, column_index as i32, 0, 1, 1);
+        }
+
+        if state_ref.people.is_empty() {
+            let empty = Label::new(Some("no stored sessions in this range"));
+            empty.set_halign(Align::Start);
+            grid.attach(&empty, 0, 1, PersonAttendanceColumn::ALL.len() as i32, 1);
+            return;
+        }
+
+        for (row_index, person) in state_ref.people.iter().enumerate() {
+            for (column_index, column) in PersonAttendanceColumn::ALL.into_iter().enumerate() {
+                let label = Label::new(Some(&column.cell_text(person)));
+                if column == PersonAttendanceColumn::Name {
+                    label.set_xalign(0.0);
+                } else {
+                    label.set_xalign(1.0);
+                }
+                grid.attach(&label, column_index as i32, row_index as i32 + 1, 1, 1);
+            }
+        }
+    }
+
+    fn build(parent: &impl IsA<Widget>) -> Window {
+        let window = Window::builder()
+            .title("attendance history")
+            .default_width(620)
+            .default_height(460)
+            .build();
+
+        if let Some(parent) = parent
+            .root()
+            .and_then(|root| root.downcast::<Window>().ok())
+        {
+            window.set_transient_for(Some(&parent));
+        }
+
+        window
+    }
+}
@@ -1,5 +1,5 @@
 use gtk4::prelude::*;
-use gtk4::{Box as GtkBox, Button, ComboBoxText, Entry, Label, Orientation, Window};
+use gtk4::{Box as GtkBox, Button, ComboBoxText, Entry, Label, Orientation, Widget, Window};
 
 use crate::core::{CellKind, Subject};
 
@@ -52,7 +52,7 @@ impl CellEditDraft {
 pub struct CellEditDialog;
 
 impl CellEditDialog {
-    pub fn present<F>(cell: &GtkBox, initial: CellEditDraft, on_save: F)
+    pub fn present<F>(cell: &impl IsA<Widget>, initial: CellEditDraft, on_save: F)
     where
         F: Fn(CellEditDraft) + 'static,
     {
@@ -141,7 +141,7 @@ impl CellEditDialog {
         window.present();
     }
 
-    fn build(cell: &GtkBox) -> Window {
+    fn build(cell: &impl IsA<Widget>) -> Window {
         let window = Window::builder()
             .modal(true)
             .title("编辑单元格")
@@ -1,10 +1,20 @@
-use crate::core::Subject;
+use crate::core::{AttendanceStatus, Subject};
+
+/// Which `.cell-surface` rule colors a cell; the colors come from the theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellFill {
+    Status(AttendanceStatus),
+    Blocked,
+    EditPending,
+    /// Not painted at all.
+    Transparent,
+}
 
 pub trait Cell {
     fn has_name(&self) -> Option<&String>;
     fn type_name(&self) -> &str;
-    fn render_color_edit_mode(&self) -> &str;
-    fn render_color_check_mode(&self) -> &str;
+    fn edit_mode_fill(&self) -> CellFill;
+    fn check_mode_fill(&self, status: AttendanceStatus) -> CellFill;
 }
 
 pub struct NormalCell {
@@ -26,12 +36,12 @@ impl Cell for NormalCell {
         "Active"
     }
 
-    fn render_color_edit_mode(&self) -> &str {
-        "#e2e8f0"
+    fn edit_mode_fill(&self) -> CellFill {
+        CellFill::EditPending
     }
 
-    fn render_color_check_mode(&self) -> &str {
-        "#ffffff"
+    fn check_mode_fill(&self, status: AttendanceStatus) -> CellFill {
+        CellFill::Status(status)
     }
 }
 
@@ -44,12 +54,12 @@ impl Cell for TransparentCell {
         "Transparent"
     }
 
-    fn render_color_edit_mode(&self) -> &str {
-        "#475569"
+    fn edit_mode_fill(&self) -> CellFill {
+        CellFill::Blocked
     }
 
-    fn render_color_check_mode(&self) -> &str {
-        "transparent"
+    fn check_mode_fill(&self, _: AttendanceStatus) -> CellFill {
+        CellFill::Transparent
     }
 }
 
@@ -62,12 +72,12 @@ impl Cell for BlockCell {
         "Block"
     }
 
-    fn render_color_edit_mode(&self) -> &str {
-        "#475569"
+    fn edit_mode_fill(&self) -> CellFill {
+        CellFill::Blocked
     }
 
-    fn render_color_check_mode(&self) -> &str {
-        "#475569"
+    fn check_mode_fill(&self, _: AttendanceStatus) -> CellFill {
+        CellFill::Blocked
     }
 }
 
@@ -0,0 +1,38 @@
+use std::path::Path;
+
+use gtk4::prelude::*;
+use gtk4::{gio, glib, Widget};
+
+/// File monitor for the table config, tied to the lifetime of a widget.
+pub struct ConfigWatcher;
+
+impl ConfigWatcher {
+    /// Calls `on_changed` after the config file is rewritten or replaced on disk.
+    ///
+    /// Monitoring stops when `owner` is destroyed.
+    pub fn attach<F>(
+        owner: &impl IsA<Widget>,
+        config_file: &Path,
+        on_changed: F,
+    ) -> Result<(), glib::Error>
+    where
+        F: Fn() + 'static,
+    {
+        let monitor = gio::File::for_path(config_file)
+            .monitor_file(gio::FileMonitorFlags::NONE, None::<&gio::Cancellable>)?;
+        monitor.connect_changed(move |_, _, _, event| {
+            // Atomic writers show up as `Created` (rename over the file), in-place
+            // writers as `ChangesDoneHint` once the file is closed.
+            if matches!(
+                event,
+                gio::FileMonitorEvent::ChangesDoneHint | gio::FileMonitorEvent::Created
+            ) {
+                on_changed();
+            }
+        });
+        owner.connect_destroy(move |_| {
+            monitor.cancel();
+        });
+        Ok(())
+    }
+}
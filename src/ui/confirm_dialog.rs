@@ -0,0 +1,84 @@
+use gtk4::prelude::*;
+use gtk4::{accessible, Box as GtkBox, Button, Label, Orientation, Widget, Window};
+
+/// Standalone window asking the user to confirm an action that is hard to undo.
+pub struct ConfirmDialog;
+
+impl ConfirmDialog {
+    /// Shows `question` with `detail` below it; `on_confirm` runs only when the
+    /// `confirm_label` button is pressed. Focus starts on cancel.
+    pub fn present<F>(
+        parent: &impl IsA<Widget>,
+        question: &str,
+        detail: &str,
+        confirm_label: &str,
+        on_confirm: F,
+    ) where
+        F: Fn() + 'static,
+    {
+        let window = Self::build(parent);
+        window.update_property(&[
+            accessible::Property::Label(question),
+            accessible::Property::Description(detail),
+        ]);
+
+        let content = GtkBox::new(Orientation::Vertical, 10);
+        content.set_margin_top(14);
+        content.set_margin_bottom(14);
+        content.set_margin_start(14);
+        content.set_margin_end(14);
+
+        let heading = Label::new(Some(question));
+        heading.set_xalign(0.0);
+        heading.set_wrap(true);
+        content.append(&heading);
+        let detail_label = Label::new(Some(detail));
+        detail_label.set_xalign(0.0);
+        detail_label.set_wrap(true);
+        content.append(&detail_label);
+
+        let actions = GtkBox::new(Orientation::Horizontal, 8);
+        actions.set_halign(gtk4::Align::End);
+        let cancel_button = Button::with_label("cancel");
+        {
+            let window = window.clone();
+            cancel_button.connect_clicked(move |_| {
+                window.close();
+            });
+        }
+        let confirm_button = Button::with_label(confirm_label);
+        confirm_button.add_css_class("destructive-action");
+        {
+            let window = window.clone();
+            confirm_button.connect_clicked(move |_| {
+                window.close();
+                on_confirm();
+            });
+        }
+        actions.append(&cancel_button);
+        actions.append(&confirm_button);
+        content.append(&actions);
+
+        window.set_child(Some(&content));
+        window.present();
+        cancel_button.grab_focus();
+    }
+
+    fn build(parent: &impl IsA<Widget>) -> Window {
+        let window = Window::builder()
+            .modal(true)
+            .title("checkin")
+            .default_width(360)
+            .default_height(120)
+            .build();
+
+        if let Some(parent) = parent
+            .root()
+            .and_then(|root| root.downcast::<Window>().ok())
+        {
+            window.set_transient_for(Some(&parent));
+        }
+
+        window
+    }
+}
@@ -0,0 +1,122 @@
+use gtk4::prelude::*;
+use gtk4::{Align, Box as GtkBox, Button, Label, Orientation, Widget};
+
+use crate::core::ConfigDiagnostic;
+
+const CLASS_BANNER: &str = "diagnostics-banner";
+const CLASS_BANNER_ERROR: &str = "has-errors";
+const CLASS_TITLE: &str = "diagnostics-title";
+const CLASS_DETAIL: &str = "diagnostics-detail";
+
+/// Dismissible banner listing config validation findings.
+///
+/// Hidden while there is nothing to report.
+#[derive(Clone)]
+pub struct DiagnosticsBanner {
+    root: GtkBox,
+    title_label: Label,
+    detail_label: Label,
+    actions: GtkBox,
+}
+
+impl DiagnosticsBanner {
+    pub fn new() -> Self {
+        let root = GtkBox::new(Orientation::Horizontal, 8);
+        root.add_css_class(CLASS_BANNER);
+        root.set_visible(false);
+
+        let text = GtkBox::new(Orientation::Vertical, 2);
+        text.set_hexpand(true);
+
+        let title_label = Label::new(None);
+        title_label.add_css_class(CLASS_TITLE);
+        title_label.set_xalign(0.0);
+
+        let detail_label = Label::new(None);
+        detail_label.add_css_class(CLASS_DETAIL);
+        detail_label.set_xalign(0.0);
+        detail_label.set_wrap(true);
+        detail_label.set_selectable(true);
+
+        text.append(&title_label);
+        text.append(&detail_label);
+
+        let actions = GtkBox::new(Orientation::Horizontal, 6);
+        actions.set_valign(Align::Start);
+
+        let dismiss_button = Button::with_label("×");
+        dismiss_button.set_valign(Align::Start);
+        {
+            let root = root.clone();
+            dismiss_button.connect_clicked(move |_| {
+                root.set_visible(false);
+            });
+        }
+
+        root.append(&text);
+        root.append(&actions);
+        root.append(&dismiss_button);
+
+        Self {
+            root,
+            title_label,
+            detail_label,
+            actions,
+        }
+    }
+
+    pub fn widget(&self) -> &GtkBox {
+        &self.root
+    }
+
+    /// Replaces the action area (e.g. a restore control) shown beside the findings.
+    pub fn set_actions(&self, widget: Option<&Widget>) {
+        while let Some(child) = self.actions.first_child() {
+            self.actions.remove(&child);
+        }
+        if let Some(widget) = widget {
+            self.actions.append(widget);
+        }
+    }
+
+    /// Hides the banner and drops any action widgets.
+    pub fn dismiss(&self) {
+        self.set_actions(None);
+        self.root.set_visible(false);
+    }
+
+    /// Shows a free-form notice, e.g. about an external config change.
+    pub fn show_message(&self, title: &str, detail: &str, is_error: bool) {
+        if is_error {
+            self.root.add_css_class(CLASS_BANNER_ERROR);
+        } else {
+            self.root.remove_css_class(CLASS_BANNER_ERROR);
+        }
+        self.title_label.set_text(title);
+        self.detail_label.set_text(detail);
+        self.root.set_visible(true);
+    }
+
+    /// Replaces the listed findings and shows the banner when there are any.
+    pub fn show(&self, source: &str, diagnostics: &[ConfigDiagnostic]) {
+        if diagnostics.is_empty() {
+            self.root.set_visible(false);
+            return;
+        }
+
+        let errors = diagnostics.iter().filter(|item| item.is_error()).count();
+        let warnings = diagnostics.len() - errors;
+        if errors > 0 {
+            self.root.add_css_class(CLASS_BANNER_ERROR);
+        } else {
+            self.root.remove_css_class(CLASS_BANNER_ERROR);
+        }
+
+        self.title_label.set_text(&format!(
+            "{source}: {errors} error(s), {warnings} warning(s)"
+        ));
+        let lines: Vec<String> = diagnostics.iter().map(ToString::to_string).collect();
+        self.detail_label.set_text(&lines.join("\n"));
+        self.root.set_visible(true);
+    }
+}
@@ -1,12 +1,26 @@
+mod api_bridge;
 mod app_view;
+mod attendance_history_dialog;
 mod cell_edit_dialog;
 mod cell_model;
+mod config_watcher;
+mod confirm_dialog;
 mod corner_add_button;
+mod diagnostics_banner;
 mod mode_switch;
+mod picker_panel;
+mod qr_code_view;
+mod roll_call_dialog;
+mod scan_panel;
+mod seat_canvas;
+mod self_checkin_panel;
 mod statistics_panel;
 mod status_dialog;
+mod system_appearance;
 mod table_cell;
+mod table_header;
 mod table_view;
+mod trend_chart;
 mod ui_styles;
 
 pub use app_view::AppView;
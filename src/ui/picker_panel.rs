@@ -0,0 +1,144 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use gtk4::prelude::*;
+use gtk4::{glib, Box as GtkBox, Button, CheckButton, Label, MenuButton, Orientation, Popover};
+use rand::seq::SliceRandom;
+
+use crate::core::{Position, RandomPicker, Subject};
+use crate::ui::table_view::TableView;
+
+const CLASS_RESULT: &str = "picker-result";
+/// Highlight hops shown before landing on the picked seat.
+const ANIMATION_STEPS: usize = 14;
+const FIRST_STEP_DELAY: Duration = Duration::from_millis(50);
+const STEP_SLOWDOWN: Duration = Duration::from_millis(14);
+const RESULT_HIGHLIGHT: Duration = Duration::from_secs(4);
+
+/// "pick someone" button that draws a random `Checked` person.
+///
+/// The highlight hops across present seats and slows down before stopping on the
+/// pick, the way a wheel of names would.
+#[derive(Clone)]
+pub struct PickerPanel {
+    root: GtkBox,
+    pick_button: Button,
+    result_label: Label,
+    picker: Rc<RefCell<RandomPicker>>,
+    table_view: Rc<TableView>,
+}
+
+impl PickerPanel {
+    pub fn new(table_view: Rc<TableView>) -> Self {
+        let root = GtkBox::new(Orientation::Horizontal, 4);
+        let pick_button = Button::with_label("pick someone");
+        let result_label = Label::new(None);
+        result_label.add_css_class(CLASS_RESULT);
+
+        let picker = Rc::new(RefCell::new(RandomPicker::new()));
+        let options = GtkBox::new(Orientation::Vertical, 6);
+        options.set_margin_top(8);
+        options.set_margin_bottom(8);
+        options.set_margin_start(8);
+        options.set_margin_end(8);
+        let avoid_repeats = CheckButton::with_label("no repeats until everyone had a turn");
+        let weight_by_history = CheckButton::with_label("favor people picked less often");
+        let reset_button = Button::with_label("forget previous picks");
+        options.append(&avoid_repeats);
+        options.append(&weight_by_history);
+        options.append(&reset_button);
+        let popover = Popover::new();
+        popover.set_child(Some(&options));
+        let options_button = MenuButton::new();
+        options_button.set_icon_name("open-menu-symbolic");
+        options_button.set_tooltip_text(Some("picker options"));
+        options_button.set_popover(Some(&popover));
+
+        root.append(&pick_button);
+        root.append(&options_button);
+        root.append(&result_label);
+
+        {
+            let picker = Rc::clone(&picker);
+            avoid_repeats.connect_toggled(move |button| {
+                picker.borrow_mut().avoid_repeats = button.is_active();
+            });
+        }
+        {
+            let picker = Rc::clone(&picker);
+            weight_by_history.connect_toggled(move |button| {
+                picker.borrow_mut().weight_by_history = button.is_active();
+            });
+        }
+        {
+            let picker = Rc::clone(&picker);
+            reset_button.connect_clicked(move |_| picker.borrow_mut().reset());
+        }
+
+        let panel = Self {
+            root,
+            pick_button,
+            result_label,
+            picker,
+            table_view,
+        };
+        {
+            let panel_for_click = panel.clone();
+            panel
+                .pick_button
+                .connect_clicked(move |_| panel_for_click.pick());
+        }
+        panel
+    }
+
+    pub fn widget(&self) -> &GtkBox {
+        &self.root
+    }
+
+    fn pick(&self) {
+        let table = self.table_view.table();
+        let attendance = self.table_view.attendance();
+        let mut rng = rand::thread_rng();
+        let candidates = self.picker.borrow().candidates(&table, &attendance);
+        let Some(picked) = self.picker.borrow_mut().pick(&table, &attendance, &mut rng) else {
+            self.result_label.set_text("nobody is checked in");
+            return;
+        };
+
+        let mut frames: Vec<Position> = (0..ANIMATION_STEPS)
+            .filter_map(|_| candidates.choose(&mut rng).copied())
+            .collect();
+        frames.push(picked);
+        let name = table
+            .subject_at(picked)
+            .and_then(Subject::name)
+            .cloned()
+            .unwrap_or_default();
+
+        self.pick_button.set_sensitive(false);
+        self.result_label.set_text("…");
+        self.play(Rc::new(frames), 0, Rc::new(name));
+    }
+
+    fn play(&self, frames: Rc<Vec<Position>>, index: usize, name: Rc<String>) {
+        self.table_view.set_highlighted(frames.get(index).copied());
+        if index + 1 < frames.len() {
+            let panel = self.clone();
+            let delay = FIRST_STEP_DELAY + STEP_SLOWDOWN * index as u32;
+            glib::timeout_add_local_once(delay, move || panel.play(frames, index + 1, name));
+            return;
+        }
+
+        self.result_label.set_text(&name);
+        self.pick_button.set_sensitive(true);
+        let table_view = Rc::clone(&self.table_view);
+        let picked = frames.last().copied();
+        glib::timeout_add_local_once(RESULT_HIGHLIGHT, move || {
+            // A later pick or roll call may own the highlight by now.
+            if table_view.highlighted() == picked {
+                table_view.set_highlighted(None);
+            }
+        });
+    }
+}
@@ -0,0 +1,98 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::prelude::*;
+use gtk4::{cairo, DrawingArea};
+use qrcode::{Color, QrCode};
+
+const CLASS_QR_CODE: &str = "qr-code";
+const QUIET_ZONE_MODULES: usize = 4;
+const MIN_SIZE: i32 = 144;
+
+/// Square QR code drawn with cairo; hidden while there is nothing to encode.
+#[derive(Clone)]
+pub struct QrCodeView {
+    area: DrawingArea,
+    code: Rc<RefCell<Option<QrCode>>>,
+}
+
+impl QrCodeView {
+    pub fn new() -> Self {
+        let area = DrawingArea::new();
+        area.add_css_class(CLASS_QR_CODE);
+        area.set_content_width(MIN_SIZE);
+        area.set_content_height(MIN_SIZE);
+        area.set_visible(false);
+
+        let code: Rc<RefCell<Option<QrCode>>> = Rc::new(RefCell::new(None));
+        {
+            let code = Rc::clone(&code);
+            area.set_draw_func(move |_, context, width, height| {
+                let code = code.borrow();
+                let Some(code) = code.as_ref() else {
+                    return;
+                };
+                if let Err(error) = Self::draw(context, code, width, height) {
+                    eprintln!("failed to draw QR code: {error}");
+                }
+            });
+        }
+
+        Self { area, code }
+    }
+
+    pub fn widget(&self) -> &DrawingArea {
+        &self.area
+    }
+
+    /// Encodes `text` and shows it; `None` hides the widget.
+    pub fn set_text(&self, text: Option<&str>) {
+        let code = text.and_then(|text| match QrCode::new(text.as_bytes()) {
+            Ok(code) => Some(code),
+            Err(error) => {
+                eprintln!("cannot encode QR code: {error}");
+                None
+            }
+        });
+        self.area.set_visible(code.is_some());
+        self.code.replace(code);
+        self.area.queue_draw();
+    }
+
+    fn draw(
+        context: &cairo::Context,
+        code: &QrCode,
+        width: i32,
+        height: i32,
+    ) -> Result<(), cairo::Error> {
+        let modules = code.width() + QUIET_ZONE_MODULES * 2;
+        let side = f64::from(width.min(height));
+        let module_size = (side / modules as f64).floor().max(1.0);
+        let origin_x = (f64::from(width) - module_size * modules as f64) / 2.0;
+        let origin_y = (f64::from(height) - module_size * modules as f64) / 2.0;
+
+        context.set_source_rgb(1.0, 1.0, 1.0);
+        context.rectangle(
+            origin_x,
+            origin_y,
+            module_size * modules as f64,
+            module_size * modules as f64,
+        );
+        context.fill()?;
+
+        context.set_source_rgb(0.0, 0.0, 0.0);
+        for y in 0..code.width() {
+            for x in 0..code.width() {
+                if code[(x, y)] == Color::Dark {
+                    context.rectangle(
+                        origin_x + (x + QUIET_ZONE_MODULES) as f64 * module_size,
+                        origin_y + (y + QUIET_ZONE_MODULES) as f64 * module_size,
+                        module_size,
+                        module_size,
+                    );
+                }
+            }
+        }
+        context.fill()
+    }
+}
@@ -0,0 +1,217 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::prelude::*;
+use gtk4::{
+    gdk, glib, Box as GtkBox, Button, DropDown, EventControllerKey, Label, Orientation,
+    PropagationPhase, Widget, Window,
+};
+
+use crate::core::{RollCall, RollCallAnswer, RollCallOrder};
+use crate::ui::table_view::TableView;
+
+const CLASS_NAME: &str = "roll-call-name";
+const CLASS_PROGRESS: &str = "roll-call-progress";
+
+/// Window for calling names out loud, one seat at a time.
+///
+/// `p`/`a`/`l` answer present/absent/leave and advance; Backspace or Left goes back,
+/// Space or Right skips and Escape closes. The seat being called is highlighted on
+/// the board.
+pub struct RollCallDialog;
+
+struct RollCallWidgets {
+    name_label: Label,
+    progress_label: Label,
+    answer_buttons: Vec<Button>,
+    back_button: Button,
+}
+
+impl RollCallDialog {
+    pub fn present(parent: &impl IsA<Widget>, table_view: Rc<TableView>) {
+        let window = Self::build(parent);
+        let roll_call = Rc::new(RefCell::new(RollCall::new(
+            &table_view.table(),
+            RollCallOrder::default(),
+        )));
+
+        let content = GtkBox::new(Orientation::Vertical, 12);
+        content.set_margin_top(16);
+        content.set_margin_bottom(16);
+        content.set_margin_start(16);
+        content.set_margin_end(16);
+
+        let order_picker = DropDown::from_strings(&RollCallOrder::ALL.map(RollCallOrder::label));
+        let progress_label = Label::new(None);
+        progress_label.add_css_class(CLASS_PROGRESS);
+        let name_label = Label::new(None);
+        name_label.add_css_class(CLASS_NAME);
+        name_label.set_wrap(true);
+        name_label.set_vexpand(true);
+
+        let answers = GtkBox::new(Orientation::Horizontal, 8);
+        answers.set_homogeneous(true);
+        let answer_buttons: Vec<Button> = RollCallAnswer::ALL
+            .into_iter()
+            .map(|answer| {
+                let button = Button::with_label(&format!("{} ({})", answer.label(), answer.key()));
+                answers.append(&button);
+                button
+            })
+            .collect();
+
+        let navigation = GtkBox::new(Orientation::Horizontal, 8);
+        let back_button = Button::with_label("back");
+        let skip_button = Button::with_label("skip");
+        navigation.append(&back_button);
+        navigation.append(&skip_button);
+
+        content.append(&order_picker);
+        content.append(&progress_label);
+        content.append(&name_label);
+        content.append(&answers);
+        content.append(&navigation);
+
+        let widgets = Rc::new(RollCallWidgets {
+            name_label,
+            progress_label,
+            answer_buttons,
+            back_button: back_button.clone(),
+        });
+        let answer: Rc<dyn Fn(RollCallAnswer)> = {
+            let roll_call = Rc::clone(&roll_call);
+            let table_view = Rc::clone(&table_view);
+            let widgets = Rc::clone(&widgets);
+            Rc::new(move |answer| {
+                table_view.answer_roll_call(&mut roll_call.borrow_mut(), answer);
+                Self::render(&roll_call.borrow(), &table_view, &widgets);
+            })
+        };
+        let step: Rc<dyn Fn(bool)> = {
+            let roll_call = Rc::clone(&roll_call);
+            let table_view = Rc::clone(&table_view);
+            let widgets = Rc::clone(&widgets);
+            Rc::new(move |forward| {
+                if forward {
+                    roll_call.borrow_mut().advance();
+                } else {
+                    roll_call.borrow_mut().back();
+                }
+                Self::render(&roll_call.borrow(), &table_view, &widgets);
+            })
+        };
+
+        for (button, value) in widgets.answer_buttons.iter().zip(RollCallAnswer::ALL) {
+            let answer = Rc::clone(&answer);
+            button.connect_clicked(move |_| answer(value));
+        }
+        {
+            let step = Rc::clone(&step);
+            back_button.connect_clicked(move |_| step(false));
+        }
+        {
+            let step = Rc::clone(&step);
+            skip_button.connect_clicked(move |_| step(true));
+        }
+        {
+            let roll_call = Rc::clone(&roll_call);
+            let table_view = Rc::clone(&table_view);
+            let widgets = Rc::clone(&widgets);
+            order_picker.connect_selected_notify(move |picker| {
+                let order = RollCallOrder::ALL
+                    .get(picker.selected() as usize)
+                    .copied()
+                    .unwrap_or_default();
+                roll_call.replace(RollCall::new(&table_view.table(), order));
+                Self::render(&roll_call.borrow(), &table_view, &widgets);
+            });
+        }
+        {
+            let window_for_keys = window.clone();
+            // Capture phase, so Space doesn't also activate the focused button.
+            let keys = EventControllerKey::new();
+            keys.set_propagation_phase(PropagationPhase::Capture);
+            keys.connect_key_pressed(move |_, key, _, modifiers| {
+                if modifiers
+                    .intersects(gdk::ModifierType::CONTROL_MASK | gdk::ModifierType::ALT_MASK)
+                {
+                    return glib::Propagation::Proceed;
+                }
+                match key {
+                    gdk::Key::BackSpace | gdk::Key::Left => step(false),
+                    gdk::Key::space | gdk::Key::Right => step(true),
+                    gdk::Key::Escape => window_for_keys.close(),
+                    _ => match key.to_unicode().and_then(RollCallAnswer::from_key) {
+                        Some(value) => answer(value),
+                        None => return glib::Propagation::Proceed,
+                    },
+                }
+                glib::Propagation::Stop
+            });
+            window.add_controller(keys);
+        }
+        {
+            let table_view = Rc::clone(&table_view);
+            window.connect_close_request(move |_| {
+                table_view.set_highlighted(None);
+                glib::Propagation::Proceed
+            });
+        }
+
+        Self::render(&roll_call.borrow(), &table_view, &widgets);
+        window.set_child(Some(&content));
+        window.present();
+    }
+
+    fn render(roll_call: &RollCall, table_view: &TableView, widgets: &RollCallWidgets) {
+        let current = roll_call.current();
+        table_view.set_highlighted(current.map(|(position, _)| position));
+
+        match current {
+            Some((position, name)) => {
+                widgets.name_label.set_text(name);
+                let status = table_view
+                    .status_at(position)
+                    .map_or("", |status| status.label());
+                widgets.progress_label.set_text(&format!(
+                    "{} / {}  ·  currently {}",
+                    roll_call.index() + 1,
+                    roll_call.len(),
+                    status
+                ));
+            }
+            None if roll_call.is_empty() => {
+                widgets.name_label.set_text("no names to call");
+                widgets.progress_label.set_text("");
+            }
+            None => {
+                widgets.name_label.set_text("done");
+                widgets
+                    .progress_label
+                    .set_text(&format!("{0} / {0}", roll_call.len()));
+            }
+        }
+
+        for button in &widgets.answer_buttons {
+            button.set_sensitive(current.is_some());
+        }
+        widgets.back_button.set_sensitive(roll_call.index() > 0);
+    }
+
+    fn build(parent: &impl IsA<Widget>) -> Window {
+        let window = Window::builder()
+            .title("roll call")
+            .default_width(420)
+            .default_height(280)
+            .build();
+
+        if let Some(parent) = parent
+            .root()
+            .and_then(|root| root.downcast::<Window>().ok())
+        {
+            window.set_transient_for(Some(&parent));
+        }
+
+        window
+    }
+}
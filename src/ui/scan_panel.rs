@@ -0,0 +1,136 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Instant;
+
+use gtk4::prelude::*;
+use gtk4::{
+    gdk, glib, Box as GtkBox, EventControllerKey, Label, Orientation, PropagationPhase,
+    ToggleButton, Widget,
+};
+
+use crate::core::{Roster, ScanBuffer};
+use crate::ui::table_view::TableView;
+
+const CLASS_PANEL: &str = "scan-panel";
+const CLASS_FEEDBACK: &str = "scan-feedback";
+const CLASS_OK: &str = "scan-ok";
+const CLASS_ATTENTION: &str = "scan-attention";
+
+/// Toolbar toggle for card scanner input, plus feedback for the last scan.
+///
+/// While active, keystroke bursts ending in Enter are treated as scanned student IDs.
+/// Unknown IDs and repeated scans ring the error bell and are highlighted.
+#[derive(Clone)]
+pub struct ScanPanel {
+    root: GtkBox,
+    toggle: ToggleButton,
+    feedback_label: Label,
+    roster: Rc<Roster>,
+    buffer: Rc<RefCell<ScanBuffer>>,
+    table_view: Rc<TableView>,
+}
+
+impl ScanPanel {
+    pub fn new(roster: Roster, table_view: Rc<TableView>) -> Self {
+        let root = GtkBox::new(Orientation::Horizontal, 8);
+        root.add_css_class(CLASS_PANEL);
+
+        let toggle = ToggleButton::with_label("scan cards");
+        let feedback_label = Label::new(None);
+        feedback_label.add_css_class(CLASS_FEEDBACK);
+        feedback_label.set_visible(false);
+
+        root.append(&toggle);
+        root.append(&feedback_label);
+
+        let panel = Self {
+            root,
+            toggle,
+            feedback_label,
+            roster: Rc::new(roster),
+            buffer: Rc::new(RefCell::new(ScanBuffer::new())),
+            table_view,
+        };
+        {
+            let panel_for_toggle = panel.clone();
+            panel.toggle.connect_toggled(move |toggle| {
+                panel_for_toggle.buffer.replace(ScanBuffer::new());
+                let feedback_label = &panel_for_toggle.feedback_label;
+                feedback_label.remove_css_class(CLASS_OK);
+                feedback_label.remove_css_class(CLASS_ATTENTION);
+                feedback_label.set_text("waiting for scans");
+                feedback_label.set_visible(toggle.is_active());
+            });
+        }
+        panel
+    }
+
+    pub fn widget(&self) -> &GtkBox {
+        &self.root
+    }
+
+    /// Scanning only applies in check-in mode; hiding the panel also stops it.
+    pub fn set_available(&self, available: bool) {
+        if !available {
+            self.toggle.set_active(false);
+        }
+        self.root.set_visible(available);
+    }
+
+    /// Listens for scanner keystrokes anywhere inside `widget`.
+    ///
+    /// Runs in the capture phase so Enter reaches the scanner before it activates
+    /// whichever button has focus.
+    pub fn attach_keys(&self, widget: &impl IsA<Widget>) {
+        let panel = self.clone();
+        let keys = EventControllerKey::new();
+        keys.set_propagation_phase(PropagationPhase::Capture);
+        keys.connect_key_pressed(move |_, key, _, modifiers| {
+            if !panel.toggle.is_active()
+                || modifiers
+                    .intersects(gdk::ModifierType::CONTROL_MASK | gdk::ModifierType::ALT_MASK)
+            {
+                return glib::Propagation::Proceed;
+            }
+
+            let now = Instant::now();
+            match key {
+                gdk::Key::Return | gdk::Key::KP_Enter => {
+                    let Some(code) = panel.buffer.borrow_mut().finish(now) else {
+                        return glib::Propagation::Proceed;
+                    };
+                    panel.apply_scan(&code);
+                    glib::Propagation::Stop
+                }
+                _ => match key.to_unicode() {
+                    Some(character) if !character.is_control() => {
+                        panel.buffer.borrow_mut().push(character, now);
+                        glib::Propagation::Stop
+                    }
+                    _ => glib::Propagation::Proceed,
+                },
+            }
+        });
+        widget.add_controller(keys);
+    }
+
+    fn apply_scan(&self, code: &str) {
+        match self.table_view.check_in_scanned(&self.roster, code) {
+            Some(outcome) => self.show_feedback(&outcome.message(), outcome.needs_attention()),
+            None => self.show_feedback("busy, scan again", true),
+        }
+    }
+
+    fn show_feedback(&self, message: &str, needs_attention: bool) {
+        self.feedback_label.set_text(message);
+        self.feedback_label.set_visible(true);
+        if needs_attention {
+            self.feedback_label.remove_css_class(CLASS_OK);
+            self.feedback_label.add_css_class(CLASS_ATTENTION);
+            self.root.error_bell();
+        } else {
+            self.feedback_label.remove_css_class(CLASS_ATTENTION);
+            self.feedback_label.add_css_class(CLASS_OK);
+        }
+    }
+}
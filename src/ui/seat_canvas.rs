@@ -0,0 +1,318 @@
+use gtk4::prelude::*;
+use gtk4::{accessible, cairo, glib, AccessibleRole, Align, DrawingArea};
+
+use crate::core::{
+    AppMode, AttendanceBook, AttendanceStatus, CellSpan, Position, Rgba, Table, Theme,
+};
+use crate::ui::cell_model::CellFill;
+use crate::ui::table_cell::{CellLook, TableCell};
+use crate::ui::ui_styles::{board_font_size, connect_theme_changed, current_theme};
+
+/// Space between cells, matching the widget board's grid spacing.
+pub const CELL_GAP: f64 = 1.0;
+const BORDER_WIDTH: f64 = 2.0;
+const HIGHLIGHT_WIDTH: f64 = 3.0;
+const FOCUS_DASHES: [f64; 2] = [4.0, 2.0];
+/// Below this cell height names are left out; the colors still show the status.
+const MIN_TEXT_CELL_HEIGHT: f64 = 10.0;
+
+/// Everything the drawn board needs for one frame.
+pub struct CanvasScene<'a> {
+    pub table: &'a Table,
+    pub attendance: &'a AttendanceBook,
+    pub mode: AppMode,
+    /// Edit-mode selection as `(anchor, focus)`; both corners are inclusive.
+    pub selection: Option<(Position, Position)>,
+    pub highlighted: Option<Position>,
+    /// Seat with the keyboard focus, outlined while the board has focus.
+    pub focused: Option<Position>,
+    /// Board scale, used for the name size.
+    pub scale: f64,
+}
+
+/// The seating board as a single widget that paints every cell with cairo.
+///
+/// Built for layouts with thousands of seats, where one widget per cell gets slow.
+/// Cells use the same look as [`TableCell`]; clicks are mapped back to positions with
+/// [`SeatCanvas::position_at`]. Screen readers see one grid whose name is the focused seat.
+#[derive(Clone)]
+pub struct SeatCanvas {
+    area: DrawingArea,
+}
+
+impl SeatCanvas {
+    pub fn new() -> Self {
+        let area = DrawingArea::builder()
+            .accessible_role(AccessibleRole::Grid)
+            .hexpand(true)
+            .vexpand(true)
+            .halign(Align::Fill)
+            .valign(Align::Fill)
+            .focusable(true)
+            .build();
+        area.update_property(&[accessible::Property::Description(
+            "Arrow keys move between seats, Space selects, Enter sets the status or edits",
+        )]);
+        let weak_area = area.downgrade();
+        connect_theme_changed(move || match weak_area.upgrade() {
+            Some(area) => {
+                area.queue_draw();
+                glib::ControlFlow::Continue
+            }
+            None => glib::ControlFlow::Break,
+        });
+        Self { area }
+    }
+
+    pub fn widget(&self) -> &DrawingArea {
+        &self.area
+    }
+
+    /// Sets what to paint; `draw` is called with the canvas width and height.
+    pub fn set_draw_func<F>(&self, draw: F)
+    where
+        F: Fn(&cairo::Context, f64, f64) -> Result<(), cairo::Error> + 'static,
+    {
+        self.area.set_draw_func(move |_, cr, width, height| {
+            if let Err(error) = draw(cr, f64::from(width), f64::from(height)) {
+                eprintln!("failed to draw seat board: {error}");
+            }
+        });
+    }
+
+    /// Sets the hover tooltip; `tooltip` is called with the canvas width and height and
+    /// the pointer position, and `None` shows no tooltip.
+    pub fn set_tooltip_func<F>(&self, tooltip: F)
+    where
+        F: Fn(f64, f64, f64, f64) -> Option<String> + 'static,
+    {
+        self.area.set_has_tooltip(true);
+        self.area
+            .connect_query_tooltip(move |area, x, y, _, tooltip_widget| {
+                let text = tooltip(
+                    f64::from(area.width()),
+                    f64::from(area.height()),
+                    f64::from(x),
+                    f64::from(y),
+                );
+                tooltip_widget.set_text(text.as_deref());
+                text.is_some()
+            });
+    }
+
+    pub fn queue_draw(&self) {
+        self.area.queue_draw();
+    }
+
+    /// The cell under a point, resolved to the top-left corner of merged cells.
+    pub fn position_at(table: &Table, width: f64, height: f64, x: f64, y: f64) -> Option<Position> {
+        let (cell_width, cell_height) = Self::cell_size(table, width, height)?;
+        if x < 0.0 || y < 0.0 {
+            return None;
+        }
+        let column = (x / (cell_width + CELL_GAP)).floor() as u32;
+        let row = (y / (cell_height + CELL_GAP)).floor() as u32;
+        let position = Position {
+            x: column.min(table.column_count() - 1),
+            y: row.min(table.row_count() - 1),
+        };
+        Some(table.cell_origin(position))
+    }
+
+    /// The note on the seat under a point, for the hover tooltip.
+    pub fn note_at<'a>(
+        table: &Table,
+        attendance: &'a AttendanceBook,
+        width: f64,
+        height: f64,
+        x: f64,
+        y: f64,
+    ) -> Option<&'a str> {
+        Self::position_at(table, width, height, x, y)
+            .and_then(|position| attendance.note_at(position))
+    }
+
+    pub fn paint(
+        cr: &cairo::Context,
+        scene: &CanvasScene<'_>,
+        width: f64,
+        height: f64,
+    ) -> Result<(), cairo::Error> {
+        let Some((cell_width, cell_height)) = Self::cell_size(scene.table, width, height) else {
+            return Ok(());
+        };
+        let theme = current_theme();
+        let font_size = board_font_size(scene.scale).min(cell_height * 0.6);
+        let show_text = cell_height >= MIN_TEXT_CELL_HEIGHT;
+
+        for position in scene.table.iter_positions() {
+            if scene.table.is_covered(position) {
+                continue;
+            }
+            let span = scene.table.span_at(position);
+            let bounds = Self::cell_bounds(position, span, cell_width, cell_height);
+            let look = TableCell::look(
+                scene.mode,
+                scene.table.subject_at(position),
+                scene.attendance.status_at(position),
+            );
+            if look.fill == CellFill::Transparent {
+                continue;
+            }
+
+            Self::paint_fill(cr, &theme, &look, bounds)?;
+            if show_text {
+                Self::paint_text(cr, &theme, &look, bounds, font_size)?;
+            }
+            if Self::is_selected(scene.selection, position) {
+                Self::stroke_inside(cr, bounds, theme.selected, BORDER_WIDTH, &[])?;
+            }
+            if scene.highlighted == Some(position) {
+                Self::stroke_inside(cr, bounds, theme.highlighted, HIGHLIGHT_WIDTH, &[])?;
+            }
+            if scene.focused == Some(position) {
+                Self::stroke_inside(cr, bounds, theme.text, BORDER_WIDTH, &FOCUS_DASHES)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn cell_size(table: &Table, width: f64, height: f64) -> Option<(f64, f64)> {
+        let columns = f64::from(table.column_count());
+        let rows = f64::from(table.row_count());
+        if columns == 0.0 || rows == 0.0 {
+            return None;
+        }
+        let cell_width = (width - CELL_GAP * (columns - 1.0)) / columns;
+        let cell_height = (height - CELL_GAP * (rows - 1.0)) / rows;
+        (cell_width > 0.0 && cell_height > 0.0).then_some((cell_width, cell_height))
+    }
+
+    /// `(x, y, width, height)` of a cell, covering the gaps inside merged cells.
+    fn cell_bounds(
+        position: Position,
+        span: CellSpan,
+        cell_width: f64,
+        cell_height: f64,
+    ) -> (f64, f64, f64, f64) {
+        let columns = f64::from(span.columns);
+        let rows = f64::from(span.rows);
+        (
+            f64::from(position.x) * (cell_width + CELL_GAP),
+            f64::from(position.y) * (cell_height + CELL_GAP),
+            columns * cell_width + (columns - 1.0) * CELL_GAP,
+            rows * cell_height + (rows - 1.0) * CELL_GAP,
+        )
+    }
+
+    fn is_selected(selection: Option<(Position, Position)>, position: Position) -> bool {
+        selection.is_some_and(|(anchor, focus)| {
+            (anchor.x.min(focus.x)..=anchor.x.max(focus.x)).contains(&position.x)
+                && (anchor.y.min(focus.y)..=anchor.y.max(focus.y)).contains(&position.y)
+        })
+    }
+
+    fn paint_fill(
+        cr: &cairo::Context,
+        theme: &Theme,
+        look: &CellLook,
+        bounds: (f64, f64, f64, f64),
+    ) -> Result<(), cairo::Error> {
+        let colors = match look.fill {
+            CellFill::Status(status) => theme.status(status),
+            CellFill::Blocked => theme.blocked,
+            CellFill::EditPending => theme.edit_pending,
+            CellFill::Transparent => return Ok(()),
+        };
+        let (x, y, width, height) = bounds;
+        Self::set_source(cr, colors.background);
+        cr.rectangle(x, y, width, height);
+        cr.fill()?;
+        Self::stroke_inside(
+            cr,
+            bounds,
+            colors.border,
+            BORDER_WIDTH,
+            border_dashes(look.fill),
+        )
+    }
+
+    fn paint_text(
+        cr: &cairo::Context,
+        theme: &Theme,
+        look: &CellLook,
+        bounds: (f64, f64, f64, f64),
+        font_size: f64,
+    ) -> Result<(), cairo::Error> {
+        if look.text.is_empty() {
+            return Ok(());
+        }
+        let foreground = match look.fill {
+            _ if look.muted => theme.muted_text,
+            CellFill::Status(status) => theme.status(status).foreground,
+            CellFill::Blocked => theme.blocked.foreground,
+            CellFill::EditPending | CellFill::Transparent => theme.edit_pending.foreground,
+        };
+        let weight = if look.bold {
+            cairo::FontWeight::Bold
+        } else {
+            cairo::FontWeight::Normal
+        };
+
+        let (x, y, width, height) = bounds;
+        cr.save()?;
+        cr.rectangle(x, y, width, height);
+        cr.clip();
+        cr.select_font_face("Sans", cairo::FontSlant::Normal, weight);
+        cr.set_font_size(font_size);
+        Self::set_source(cr, foreground);
+        let extents = cr.text_extents(&look.text)?;
+        // Centred like the widget labels; names wider than the cell are clipped.
+        let left = x + ((width - extents.width()) / 2.0).max(BORDER_WIDTH) - extents.x_bearing();
+        let baseline = y + (height - extents.height()) / 2.0 - extents.y_bearing();
+        cr.move_to(left, baseline);
+        cr.show_text(&look.text)?;
+        cr.restore()
+    }
+
+    fn stroke_inside(
+        cr: &cairo::Context,
+        bounds: (f64, f64, f64, f64),
+        color: Rgba,
+        line_width: f64,
+        dashes: &[f64],
+    ) -> Result<(), cairo::Error> {
+        let (x, y, width, height) = bounds;
+        let inset = line_width / 2.0;
+        if width <= line_width || height <= line_width {
+            return Ok(());
+        }
+        Self::set_source(cr, color);
+        cr.set_line_width(line_width);
+        cr.set_dash(dashes, 0.0);
+        cr.rectangle(
+            x + inset,
+            y + inset,
+            width - line_width,
+            height - line_width,
+        );
+        cr.stroke()?;
+        cr.set_dash(&[], 0.0);
+        Ok(())
+    }
+
+    fn set_source(cr: &cairo::Context, color: Rgba) {
+        let (red, green, blue, alpha) = color.fractions();
+        cr.set_source_rgba(red, green, blue, alpha);
+    }
+}
+
+/// Border dashes per status, matching the widget board's `border-style`, so the status
+/// shows without telling colors apart.
+fn border_dashes(fill: CellFill) -> &'static [f64] {
+    match fill {
+        CellFill::Status(AttendanceStatus::Unchecked) => &[6.0, 3.0],
+        CellFill::Status(AttendanceStatus::Marked) => &[2.0, 2.0],
+        _ => &[],
+    }
+}
@@ -0,0 +1,214 @@
+use std::cell::{Cell, RefCell};
+use std::net::{IpAddr, Ipv4Addr, TcpListener};
+use std::rc::Rc;
+
+use gtk4::prelude::*;
+use gtk4::{glib, Box as GtkBox, Label, Orientation, ToggleButton};
+
+use crate::core::{
+    Roster, SelfCheckIn, SelfCheckInError, PIN_ROTATION_INTERVAL, TOKEN_ROTATION_INTERVAL,
+};
+use crate::ui::api_bridge::serve_on_main_loop;
+use crate::ui::table_view::TableView;
+use crate::utilities::{local_network_address, parse_form_urlencoded, HttpResponse};
+
+const CLASS_PANEL: &str = "self-checkin-panel";
+const CLASS_PIN: &str = "self-checkin-pin";
+const CLASS_ADDRESS: &str = "self-checkin-address";
+
+type LinkChangedCallback = Rc<dyn Fn(Option<String>) + 'static>;
+
+/// Toolbar toggle that opens the self check-in page and shows its rotating PIN.
+///
+/// The page is served on all interfaces so students can reach it from their phones.
+/// The server starts the first time the toggle is switched on and stays up; while the
+/// toggle is off, submissions are rejected. Students identify themselves by name or by
+/// an ID from `roster`.
+///
+/// Link listeners get the check-in URL with the current QR token, refreshed on every
+/// token rotation, and `None` once the session closes.
+#[derive(Clone)]
+pub struct SelfCheckInPanel {
+    root: GtkBox,
+    toggle: ToggleButton,
+    pin_label: Label,
+    address_label: Label,
+    gate: Rc<RefCell<SelfCheckIn>>,
+    server_started: Rc<Cell<bool>>,
+    rotations: Rc<RefCell<Vec<glib::SourceId>>>,
+    base_url: Rc<RefCell<String>>,
+    link_changed_callbacks: Rc<RefCell<Vec<LinkChangedCallback>>>,
+}
+
+impl SelfCheckInPanel {
+    pub fn new(port: u16, roster: Roster, table_view: Rc<TableView>) -> Self {
+        let root = GtkBox::new(Orientation::Horizontal, 8);
+        root.add_css_class(CLASS_PANEL);
+
+        let toggle = ToggleButton::with_label("self check-in");
+        let pin_label = Label::new(None);
+        pin_label.add_css_class(CLASS_PIN);
+        pin_label.set_visible(false);
+        let address_label = Label::new(None);
+        address_label.add_css_class(CLASS_ADDRESS);
+        address_label.set_selectable(true);
+        address_label.set_visible(false);
+
+        root.append(&toggle);
+        root.append(&pin_label);
+        root.append(&address_label);
+
+        let mut gate = SelfCheckIn::new();
+        gate.set_roster(roster);
+        let panel = Self {
+            root,
+            toggle,
+            pin_label,
+            address_label,
+            gate: Rc::new(RefCell::new(gate)),
+            server_started: Rc::new(Cell::new(false)),
+            rotations: Rc::new(RefCell::new(Vec::new())),
+            base_url: Rc::new(RefCell::new(String::new())),
+            link_changed_callbacks: Rc::new(RefCell::new(Vec::new())),
+        };
+
+        {
+            let panel_for_toggle = panel.clone();
+            panel.toggle.connect_toggled(move |toggle| {
+                if toggle.is_active() {
+                    panel_for_toggle.open(port, &table_view);
+                } else {
+                    panel_for_toggle.close();
+                }
+            });
+        }
+        panel
+    }
+
+    pub fn widget(&self) -> &GtkBox {
+        &self.root
+    }
+
+    pub fn connect_link_changed<F>(&self, callback: F)
+    where
+        F: Fn(Option<String>) + 'static,
+    {
+        self.link_changed_callbacks
+            .borrow_mut()
+            .push(Rc::new(callback));
+    }
+
+    fn open(&self, port: u16, table_view: &Rc<TableView>) {
+        if !self.server_started.get() {
+            if let Err(error) = self.start_server(port, Rc::clone(table_view)) {
+                self.address_label
+                    .set_text(&format!("cannot listen on port {port}: {error}"));
+                self.address_label.set_visible(true);
+                self.toggle.set_active(false);
+                return;
+            }
+            self.server_started.set(true);
+        }
+
+        self.gate.borrow_mut().set_open(true);
+        let host =
+            local_network_address().map_or_else(|| "localhost".to_owned(), |ip| ip.to_string());
+        let base_url = format!("http://{host}:{port}/");
+        self.address_label.set_text(&base_url);
+        self.base_url.replace(base_url);
+        self.address_label.set_visible(true);
+        self.pin_label.set_visible(true);
+        self.render_pin();
+        self.emit_link_changed();
+
+        let pin_panel = self.clone();
+        let pin_rotation = glib::timeout_add_local(PIN_ROTATION_INTERVAL, move || {
+            pin_panel.gate.borrow_mut().rotate_pin();
+            pin_panel.render_pin();
+            glib::ControlFlow::Continue
+        });
+        let token_panel = self.clone();
+        let token_rotation = glib::timeout_add_local(TOKEN_ROTATION_INTERVAL, move || {
+            token_panel.gate.borrow_mut().rotate_token();
+            token_panel.emit_link_changed();
+            glib::ControlFlow::Continue
+        });
+        self.stop_rotations();
+        self.rotations
+            .borrow_mut()
+            .extend([pin_rotation, token_rotation]);
+    }
+
+    fn close(&self) {
+        self.gate.borrow_mut().set_open(false);
+        self.stop_rotations();
+        self.pin_label.set_visible(false);
+        if self.server_started.get() {
+            self.address_label.set_visible(false);
+        }
+        self.emit_link_changed();
+    }
+
+    fn stop_rotations(&self) {
+        for source in self.rotations.borrow_mut().drain(..) {
+            source.remove();
+        }
+    }
+
+    fn emit_link_changed(&self) {
+        let link = {
+            let gate = self.gate.borrow();
+            gate.is_open().then(|| gate.link(&self.base_url.borrow()))
+        };
+        let callbacks = self.link_changed_callbacks.borrow().clone();
+        for callback in callbacks {
+            callback(link.clone());
+        }
+    }
+
+    fn render_pin(&self) {
+        self.pin_label
+            .set_markup(&format!("PIN <b>{}</b>", self.gate.borrow().pin()));
+    }
+
+    fn start_server(&self, port: u16, table_view: Rc<TableView>) -> std::io::Result<()> {
+        let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, port))?;
+        let gate = Rc::clone(&self.gate);
+        serve_on_main_loop(
+            listener,
+            |request| match (request.method.as_str(), request.path.as_str()) {
+                ("GET", "/") => {
+                    let query = parse_form_urlencoded(&request.query);
+                    let token = query.get("token").map(String::as_str);
+                    Err(HttpResponse::html(200, SelfCheckIn::page(None, token)))
+                }
+                ("POST", "/") => {
+                    let mut fields = request.form_fields();
+                    let name = fields.remove("name").unwrap_or_default();
+                    let credential = fields
+                        .remove("token")
+                        .or_else(|| fields.remove("pin"))
+                        .unwrap_or_default();
+                    let client = request.peer.unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+                    Ok((client, name, credential))
+                }
+                (_, "/") => Err(HttpResponse::text(405, "method not allowed")),
+                _ => Err(HttpResponse::text(404, "not found")),
+            },
+            move |(client, name, credential)| {
+                let result =
+                    table_view.self_check_in(&mut gate.borrow_mut(), client, &name, &credential);
+                let status = match &result {
+                    Ok(_) => 200,
+                    Err(SelfCheckInError::Closed | SelfCheckInError::WrongPin) => 403,
+                    Err(SelfCheckInError::TooManyAttempts) => 429,
+                    Err(SelfCheckInError::DeviceAlreadyUsed) => 409,
+                    Err(SelfCheckInError::Busy) => 503,
+                    Err(_) => 400,
+                };
+                HttpResponse::html(status, SelfCheckIn::page(Some(&result), None))
+            },
+        );
+        Ok(())
+    }
+}
@@ -1,12 +1,14 @@
 use gtk4::prelude::*;
-use gtk4::{Box as GtkBox, Label, Orientation};
+use gtk4::{glib, Box as GtkBox, Expander, Label, Orientation};
 
-use crate::core::AttendanceStatistics;
+use crate::core::{AttendanceStatistics, SessionCompletion};
+use crate::ui::trend_chart::TrendChart;
 
 const CLASS_PANEL: &str = "statistics-panel";
 const CLASS_TITLE: &str = "statistics-title";
 const CLASS_SUMMARY: &str = "statistics-summary";
 const CLASS_DETAIL: &str = "statistics-detail";
+const CLASS_GROUPS: &str = "statistics-groups";
 
 /// Compact statistics panel for attendance progress.
 #[derive(Clone)]
@@ -15,6 +17,8 @@ pub struct StatisticsPanel {
     title_label: Label,
     summary_label: Label,
     detail_label: Label,
+    groups_label: Label,
+    trend_chart: TrendChart,
 }
 
 impl StatisticsPanel {
@@ -36,15 +40,29 @@ impl StatisticsPanel {
         detail_label.set_xalign(0.0);
         detail_label.set_wrap(true);
 
+        let groups_label = Label::new(None);
+        groups_label.add_css_class(CLASS_GROUPS);
+        groups_label.set_xalign(0.0);
+        groups_label.set_wrap(true);
+        groups_label.set_visible(false);
+
+        let trend_chart = TrendChart::new();
+        let trends = Expander::new(Some("trends"));
+        trends.set_child(Some(trend_chart.widget()));
+
         root.append(&title_label);
         root.append(&summary_label);
         root.append(&detail_label);
+        root.append(&groups_label);
+        root.append(&trends);
 
         let panel = Self {
             root,
             title_label,
             summary_label,
             detail_label,
+            groups_label,
+            trend_chart,
         };
         panel.update(initial);
         panel
@@ -60,8 +78,14 @@ impl StatisticsPanel {
         self.summary_label.clone()
     }
 
+    /// Completion of recent stored sessions for the trend chart, oldest first.
+    pub fn set_session_trend(&self, sessions: Vec<SessionCompletion>) {
+        self.trend_chart.set_sessions(sessions);
+    }
+
     /// Updates panel values from table statistics.
     pub fn update(&self, statistics: AttendanceStatistics) {
+        self.trend_chart.set_today(statistics);
         let completion = statistics.completed_ratio_percent();
         self.summary_label.set_markup(&format!(
             "<b>{}%</b> completed ({}/{})",
@@ -79,6 +103,25 @@ impl StatisticsPanel {
         ));
     }
 
+    /// Per-group completion, one line per group. Hidden when the table has no groups.
+    pub fn set_group_breakdown(&self, groups: &[(String, AttendanceStatistics)]) {
+        let lines: Vec<String> = groups
+            .iter()
+            .map(|(group, statistics)| {
+                format!(
+                    "{}: <b>{}%</b> ({}/{}), unchecked <b>{}</b>",
+                    glib::markup_escape_text(group),
+                    statistics.completed_ratio_percent(),
+                    statistics.completed_count(),
+                    statistics.active_total,
+                    statistics.unchecked
+                )
+            })
+            .collect();
+        self.groups_label.set_markup(&lines.join("\n"));
+        self.groups_label.set_visible(!lines.is_empty());
+    }
+
     /// Returns the title label for integration tests and advanced customization.
     pub fn title_label(&self) -> Label {
         self.title_label.clone()
@@ -1,7 +1,7 @@
 use std::rc::Rc;
 
 use gtk4::prelude::*;
-use gtk4::{Box as GtkBox, Button, Label, Orientation, Window};
+use gtk4::{accessible, Box as GtkBox, Button, Entry, Label, Orientation, Widget, Window};
 
 use crate::core::AttendanceStatus;
 
@@ -9,13 +9,29 @@ use crate::core::AttendanceStatus;
 pub struct StatusDialog;
 
 impl StatusDialog {
-    pub fn present<F>(cell: &GtkBox, surface: &Label, on_status_selected: F)
-    where
-        F: Fn(AttendanceStatus, Label) + 'static,
+    /// Asks for the status of the seat described by `seat`, which heads the dialog and is
+    /// announced by screen readers together with the choices.
+    ///
+    /// The chosen status comes with the note typed alongside it, starting from `note`;
+    /// a blank note is passed as `None`.
+    pub fn present<F>(
+        parent: &impl IsA<Widget>,
+        seat: &str,
+        note: Option<&str>,
+        on_status_selected: F,
+    ) where
+        F: Fn(AttendanceStatus, Option<String>) + 'static,
     {
-        let on_status_selected: Rc<dyn Fn(AttendanceStatus, Label)> = Rc::new(on_status_selected);
-        let window = Self::build(cell);
-        let weak_surface = surface.downgrade();
+        let on_status_selected: Rc<dyn Fn(AttendanceStatus, Option<String>)> =
+            Rc::new(on_status_selected);
+        let window = Self::build(parent);
+        let choices = AttendanceStatus::ALL
+            .map(AttendanceStatus::label)
+            .join(", ");
+        window.update_property(&[
+            accessible::Property::Label(&format!("Set status: {seat}")),
+            accessible::Property::Description(&format!("Choices: {choices}")),
+        ]);
 
         let content = GtkBox::new(Orientation::Vertical, 10);
         content.set_margin_top(14);
@@ -23,21 +39,32 @@ impl StatusDialog {
         content.set_margin_start(14);
         content.set_margin_end(14);
 
+        let heading = Label::new(Some(seat));
+        heading.set_xalign(0.0);
+        heading.set_wrap(true);
+        content.append(&heading);
+
+        let note_entry = Entry::new();
+        note_entry.set_placeholder_text(Some("Note, e.g. sick (optional)"));
+        note_entry.set_text(note.unwrap_or_default());
+        note_entry.update_property(&[accessible::Property::Label("Note")]);
+
         let actions = GtkBox::new(Orientation::Horizontal, 8);
         for status in AttendanceStatus::ALL {
-            let button = Button::with_label(status.label());
+            let button = Button::with_label(&format!("{} {}", status.symbol(), status.label()));
+            button.update_property(&[accessible::Property::Label(status.label())]);
             let window_clone = window.clone();
-            let weak_surface = weak_surface.clone();
+            let note_entry = note_entry.clone();
             let on_status_selected = Rc::clone(&on_status_selected);
             button.connect_clicked(move |_| {
-                if let Some(surface) = weak_surface.upgrade() {
-                    on_status_selected(status, surface);
-                }
+                let note = note_entry.text().trim().to_owned();
+                on_status_selected(status, Some(note).filter(|note| !note.is_empty()));
                 window_clone.close();
             });
             actions.append(&button);
         }
         content.append(&actions);
+        content.append(&note_entry);
 
         let cancel_button = Button::with_label("取消");
         {
@@ -50,9 +77,12 @@ impl StatusDialog {
 
         window.set_child(Some(&content));
         window.present();
+        if let Some(first_choice) = actions.first_child() {
+            first_choice.grab_focus();
+        }
     }
 
-    fn build(cell: &GtkBox) -> Window {
+    fn build(cell: &impl IsA<Widget>) -> Window {
         let window = Window::builder()
             .modal(true)
             .title("checkin")
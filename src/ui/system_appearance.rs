@@ -0,0 +1,106 @@
+use std::rc::Rc;
+
+use gtk4::prelude::*;
+use gtk4::{gio, glib, Settings};
+
+const PORTAL_NAME: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const PORTAL_SETTINGS: &str = "org.freedesktop.portal.Settings";
+const APPEARANCE_NAMESPACE: &str = "org.freedesktop.appearance";
+const COLOR_SCHEME_KEY: &str = "color-scheme";
+/// `color-scheme` value for "prefer dark"; 0 means no preference, 2 prefer light.
+const PREFER_DARK: u32 = 1;
+const PORTAL_TIMEOUT_MS: i32 = 500;
+
+/// The desktop's dark-mode preference.
+///
+/// Read from the settings portal's `color-scheme`, which GNOME, KDE and most other
+/// desktops publish. Without a portal, a GTK theme name containing "dark" counts.
+pub struct SystemAppearance {
+    portal: Option<gio::DBusProxy>,
+}
+
+impl SystemAppearance {
+    pub fn new() -> Self {
+        let portal = gio::DBusProxy::for_bus_sync(
+            gio::BusType::Session,
+            gio::DBusProxyFlags::DO_NOT_LOAD_PROPERTIES,
+            None,
+            PORTAL_NAME,
+            PORTAL_PATH,
+            PORTAL_SETTINGS,
+            gio::Cancellable::NONE,
+        )
+        .ok();
+        Self { portal }
+    }
+
+    pub fn prefers_dark(&self) -> bool {
+        self.portal_prefers_dark().unwrap_or_else(|| {
+            Settings::default().is_some_and(|settings| theme_name_is_dark(&settings))
+        })
+    }
+
+    /// Called with the new preference whenever the desktop switches.
+    pub fn connect_changed<F>(&self, callback: F)
+    where
+        F: Fn(bool) + 'static,
+    {
+        let callback = Rc::new(callback);
+        if let Some(portal) = &self.portal {
+            let callback = Rc::clone(&callback);
+            portal.connect_local("g-signal", false, move |values| {
+                let signal: String = values.get(2)?.get().ok()?;
+                let parameters: glib::Variant = values.get(3)?.get().ok()?;
+                if signal != "SettingChanged" {
+                    return None;
+                }
+                let (namespace, key, value) =
+                    parameters.get::<(String, String, glib::Variant)>()?;
+                if namespace == APPEARANCE_NAMESPACE && key == COLOR_SCHEME_KEY {
+                    callback(color_scheme(value) == Some(PREFER_DARK));
+                }
+                None
+            });
+        }
+
+        if let Some(settings) = Settings::default() {
+            let portal_answers = self.portal_prefers_dark().is_some();
+            settings.connect_gtk_theme_name_notify(move |settings| {
+                if !portal_answers {
+                    callback(theme_name_is_dark(settings));
+                }
+            });
+        }
+    }
+
+    /// `None` when no portal answers, e.g. outside a desktop session.
+    fn portal_prefers_dark(&self) -> Option<bool> {
+        let reply = self
+            .portal
+            .as_ref()?
+            .call_sync(
+                "Read",
+                Some(&(APPEARANCE_NAMESPACE, COLOR_SCHEME_KEY).to_variant()),
+                gio::DBusCallFlags::NONE,
+                PORTAL_TIMEOUT_MS,
+                gio::Cancellable::NONE,
+            )
+            .ok()?;
+        color_scheme(reply.child_value(0)).map(|scheme| scheme == PREFER_DARK)
+    }
+}
+
+/// Unwraps the nested variants the portal wraps values in.
+fn color_scheme(mut value: glib::Variant) -> Option<u32> {
+    while let Some(inner) = value.as_variant() {
+        value = inner;
+    }
+    value.get()
+}
+
+fn theme_name_is_dark(settings: &Settings) -> bool {
+    settings
+        .gtk_theme_name()
+        .is_some_and(|name| name.to_ascii_lowercase().contains("dark"))
+}
@@ -1,15 +1,16 @@
 use gtk4::glib;
 use gtk4::prelude::*;
-use gtk4::{Align, Box as GtkBox, Label, Orientation};
+use gtk4::{accessible, AccessibleRole, Align, Box as GtkBox, Label, Orientation};
 
 use crate::core::{AppMode, AttendanceStatus, Subject};
-use crate::ui::cell_model::{from_subject, Cell};
+use crate::ui::cell_model::{from_subject, CellFill};
 
 const CLASS_CELL: &str = "table-cell";
 const CLASS_SURFACE: &str = "cell-surface";
 const CLASS_BLOCKED: &str = "blocked";
 const CLASS_TRANSPARENT: &str = "transparent";
 const CLASS_EDIT_PENDING: &str = "edit-pending";
+const CLASS_MUTED: &str = "muted";
 
 /// View model for a single table cell (container + rendered surface).
 pub struct TableCell {
@@ -19,7 +20,11 @@ pub struct TableCell {
 
 impl TableCell {
     pub fn new() -> Self {
-        let container = GtkBox::new(Orientation::Vertical, 0);
+        let container = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .accessible_role(AccessibleRole::Button)
+            .focusable(true)
+            .build();
         container.set_hexpand(true);
         container.set_vexpand(true);
         container.set_halign(Align::Fill);
@@ -49,112 +54,157 @@ impl TableCell {
         &self.surface
     }
 
+    /// Renders a cell; `seat` is its [`Table::seat_label`](crate::core::Table::seat_label),
+    /// used for the name screen readers announce. A note shows as the tooltip.
     pub fn render_to(
         container: &GtkBox,
         surface: &Label,
+        seat: &str,
+        note: Option<&str>,
         mode: AppMode,
         subject: Option<&Subject>,
         status: Option<AttendanceStatus>,
     ) {
-        match mode {
-            AppMode::CheckIn => Self::render_check_mode(container, surface, subject, status),
-            AppMode::Edit => Self::render_edit_mode(container, surface, subject),
-        }
-    }
+        let look = Self::look(mode, subject, status);
 
-    fn clear_styles(container: &GtkBox, surface: &Label) {
         container.remove_css_class(CLASS_TRANSPARENT);
         surface.remove_css_class(CLASS_BLOCKED);
         surface.remove_css_class(CLASS_TRANSPARENT);
         surface.remove_css_class(CLASS_EDIT_PENDING);
+        surface.remove_css_class(CLASS_MUTED);
         for status in AttendanceStatus::ALL {
             surface.remove_css_class(status.css_class());
         }
+
+        match look.fill {
+            CellFill::Status(status) => surface.add_css_class(status.css_class()),
+            CellFill::Blocked => surface.add_css_class(CLASS_BLOCKED),
+            CellFill::EditPending => surface.add_css_class(CLASS_EDIT_PENDING),
+            CellFill::Transparent => {
+                container.add_css_class(CLASS_TRANSPARENT);
+                surface.add_css_class(CLASS_TRANSPARENT);
+            }
+        }
+        if look.muted {
+            surface.add_css_class(CLASS_MUTED);
+        }
+        surface.set_markup(&look.markup());
+
+        container.set_tooltip_text(note.filter(|_| mode == AppMode::CheckIn));
+        container.set_focusable(look.fill != CellFill::Transparent);
+        let usage = match mode {
+            AppMode::CheckIn => "Press Enter or double-click to set the status",
+            AppMode::Edit => "Press Space to select, Enter or double-click to edit",
+        };
+        container.update_property(&[
+            accessible::Property::Label(&Self::accessible_name(seat, note, mode, subject, status)),
+            accessible::Property::Description(usage),
+        ]);
     }
 
-    fn render_check_mode(
-        container: &GtkBox,
-        surface: &Label,
+    /// What screen readers call a cell, e.g. `Alice, row 2, seat 3, checked`, followed by
+    /// the note in check-in mode.
+    pub fn accessible_name(
+        seat: &str,
+        note: Option<&str>,
+        mode: AppMode,
         subject: Option<&Subject>,
         status: Option<AttendanceStatus>,
-    ) {
-        Self::clear_styles(container, surface);
-
-        let cell = from_subject(subject);
-        let check_color = cell.render_color_check_mode();
-
-        if check_color == "#475569" {
-            surface.add_css_class(CLASS_BLOCKED);
-        }
-        if check_color == "transparent" {
-            container.add_css_class(CLASS_TRANSPARENT);
-            surface.add_css_class(CLASS_TRANSPARENT);
-        }
-        Self::set_check_mode_text(surface, subject);
-        if !subject.is_some_and(Subject::is_inert) {
-            surface.add_css_class(status.unwrap_or_default().css_class());
+    ) -> String {
+        match subject {
+            Some(Subject::Some(name)) if mode == AppMode::CheckIn => {
+                let status = status.unwrap_or_default().label().to_lowercase();
+                match note {
+                    Some(note) => format!("{}, {seat}, {status}, {note}", name.trim()),
+                    None => format!("{}, {seat}, {status}", name.trim()),
+                }
+            }
+            Some(Subject::Some(name)) => format!("{}, {seat}", name.trim()),
+            Some(Subject::Block(name)) if !name.trim().is_empty() => {
+                format!("{}, {seat}, blocked", name.trim())
+            }
+            Some(Subject::Block(_)) => format!("Blocked, {seat}"),
+            Some(Subject::Transparent) => format!("Empty space, {seat}"),
+            None => format!("Empty seat, {seat}"),
         }
     }
 
-    fn render_edit_mode(container: &GtkBox, surface: &Label, subject: Option<&Subject>) {
-        Self::clear_styles(container, surface);
-
-        let cell = from_subject(subject);
-        let edit_color = cell.render_color_edit_mode();
-        if edit_color == "#475569" {
-            surface.add_css_class(CLASS_BLOCKED);
-        } else {
-            surface.add_css_class(CLASS_EDIT_PENDING);
+    /// The styling rules for a cell, shared with the drawn board.
+    pub fn look(
+        mode: AppMode,
+        subject: Option<&Subject>,
+        status: Option<AttendanceStatus>,
+    ) -> CellLook {
+        match mode {
+            AppMode::CheckIn => Self::check_mode_look(subject, status),
+            AppMode::Edit => Self::edit_mode_look(subject),
         }
-        Self::set_edit_mode_text(surface, &*cell);
     }
 
-    fn set_check_mode_text(surface: &Label, subject: Option<&Subject>) {
-        match subject {
+    fn check_mode_look(subject: Option<&Subject>, status: Option<AttendanceStatus>) -> CellLook {
+        let fill = from_subject(subject).check_mode_fill(status.unwrap_or_default());
+
+        let (text, muted, bold) = match subject {
             Some(Subject::Some(name)) => {
-                // Escape user-provided text before embedding in markup.
-                let escaped = glib::markup_escape_text(name);
-                surface.set_markup(&format!("<b>{escaped}</b>"));
+                let symbol = status.unwrap_or_default().symbol();
+                (format!("{symbol} {name}"), false, true)
             }
             Some(Subject::Block(name)) => {
                 let trimmed = name.trim();
-                if trimmed.is_empty() {
-                    surface.set_markup("<span foreground='#cbd5e1'>-</span>");
-                    return;
-                }
-                let escaped = glib::markup_escape_text(trimmed);
-                surface.set_markup(&format!("<span foreground='#cbd5e1'>{escaped}</span>"));
-            }
-            Some(Subject::Transparent) => {
-                surface.set_markup("");
-            }
-            None => {
-                surface.set_markup("<span foreground='#6b7280'>-</span>");
+                let text = if trimmed.is_empty() { "-" } else { trimmed };
+                (text.to_owned(), false, false)
             }
+            Some(Subject::Transparent) => (String::new(), false, false),
+            None => (String::from("-"), true, false),
+        };
+        CellLook {
+            fill,
+            text,
+            muted,
+            bold,
         }
     }
 
-    fn set_edit_mode_text(surface: &Label, cell: &dyn Cell) {
+    fn edit_mode_look(subject: Option<&Subject>) -> CellLook {
+        let cell = from_subject(subject);
         let type_name = cell.type_name();
         let maybe_text = cell
             .has_name()
             .map(|name| name.trim())
             .filter(|name| !name.is_empty());
-        let foreground = if type_name == "Active" {
-            "#334155"
-        } else {
-            "#e2e8f0"
-        };
-        let content = match (type_name, maybe_text) {
-            ("Transparent", Some(text)) => {
-                let escaped = glib::markup_escape_text(text);
-                format!("[Transparent] {escaped}")
-            }
+        let text = match (type_name, maybe_text) {
+            ("Transparent", Some(text)) => format!("[Transparent] {text}"),
             ("Transparent", None) => String::from("[Transparent]"),
-            (_, Some(text)) => glib::markup_escape_text(text).to_string(),
+            (_, Some(text)) => text.to_owned(),
             (_, None) => String::new(),
         };
-        let markup = format!("<span foreground='{foreground}'>{content}</span>");
-        surface.set_markup(&markup);
+        CellLook {
+            fill: cell.edit_mode_fill(),
+            text,
+            muted: false,
+            bold: false,
+        }
+    }
+}
+
+/// What a cell shows, independent of whether it is a widget or drawn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellLook {
+    pub fill: CellFill,
+    pub text: String,
+    /// Drawn in the theme's muted text color instead of the fill's.
+    pub muted: bool,
+    pub bold: bool,
+}
+
+impl CellLook {
+    fn markup(&self) -> String {
+        // Escape user-provided text before embedding in markup.
+        let escaped = glib::markup_escape_text(&self.text);
+        if self.bold {
+            format!("<b>{escaped}</b>")
+        } else {
+            escaped.to_string()
+        }
     }
 }
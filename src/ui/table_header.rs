@@ -0,0 +1,107 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gtk4::prelude::*;
+use gtk4::{pango, Align, Box as GtkBox, Button, Entry, Label, Orientation};
+
+use crate::core::AppMode;
+
+const CLASS_HEADER: &str = "table-header";
+const CLASS_HEADER_LABEL: &str = "table-header-label";
+
+/// Header of one row or column.
+///
+/// Check-in mode shows the label; edit mode shows an entry for it next to the
+/// remove button.
+#[derive(Clone)]
+pub struct TableHeader {
+    /// Row or column index, updated when an earlier line is removed.
+    index: Rc<Cell<u32>>,
+    root: GtkBox,
+    label: Label,
+    entry: Entry,
+    remove_button: Button,
+}
+
+impl TableHeader {
+    pub fn new(index: u32, remove_button: Button) -> Self {
+        let root = GtkBox::new(Orientation::Horizontal, 2);
+        root.add_css_class(CLASS_HEADER);
+        root.set_halign(Align::Fill);
+        root.set_valign(Align::Center);
+
+        let label = Label::new(None);
+        label.add_css_class(CLASS_HEADER_LABEL);
+        label.set_hexpand(true);
+        label.set_ellipsize(pango::EllipsizeMode::End);
+
+        let entry = Entry::new();
+        entry.set_placeholder_text(Some("label"));
+        entry.set_width_chars(6);
+        entry.set_hexpand(true);
+
+        root.append(&label);
+        root.append(&entry);
+        root.append(&remove_button);
+
+        Self {
+            index: Rc::new(Cell::new(index)),
+            root,
+            label,
+            entry,
+            remove_button,
+        }
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index.get()
+    }
+
+    pub fn set_index(&self, index: u32) {
+        self.index.set(index);
+    }
+
+    pub fn widget(&self) -> &GtkBox {
+        &self.root
+    }
+
+    /// Called with the current index when the remove button is clicked.
+    pub fn connect_remove<F>(&self, callback: F)
+    where
+        F: Fn(u32) + 'static,
+    {
+        let index = Rc::clone(&self.index);
+        self.remove_button
+            .connect_clicked(move |_| callback(index.get()));
+    }
+
+    /// Called with the current index and the trimmed entry text as it is typed, `None`
+    /// when cleared.
+    pub fn connect_label_changed<F>(&self, callback: F)
+    where
+        F: Fn(u32, Option<String>) + 'static,
+    {
+        let index = Rc::clone(&self.index);
+        self.entry.connect_changed(move |entry| {
+            let text = entry.text();
+            let text = text.trim();
+            callback(index.get(), (!text.is_empty()).then(|| text.to_owned()));
+        });
+    }
+
+    /// In check-in mode the header is only shown when `axis_labeled`, so unlabeled
+    /// tables keep their plain look.
+    pub fn render(&self, mode: AppMode, text: Option<&str>, axis_labeled: bool) {
+        let editing = mode == AppMode::Edit;
+        let text = text.unwrap_or_default();
+
+        self.label.set_text(text);
+        self.label.set_visible(!editing);
+        if self.entry.text().trim() != text {
+            self.entry.set_text(text);
+        }
+        self.entry.set_visible(editing);
+        self.remove_button.set_visible(editing);
+        self.root.set_visible(editing || axis_labeled);
+    }
+}
@@ -1,43 +1,127 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::net::IpAddr;
 use std::rc::Rc;
 use std::time::SystemTime;
 
+use gtk4::gdk;
 use gtk4::prelude::*;
-use gtk4::{Align, AspectFrame, Box as GtkBox, Button, GestureClick, Grid, Label, Widget};
+use gtk4::{
+    accessible, glib, Align, AspectFrame, Box as GtkBox, Button, EventControllerFocus,
+    EventControllerKey, EventControllerScroll, EventControllerScrollFlags, GestureClick,
+    GestureDrag, GestureZoom, Grid, Label, Orientation, PolicyType, PropagationPhase,
+    ScrolledWindow, Widget,
+};
 
-use crate::core::{AppMode, AttendanceBook, AttendanceStatistics, Position, Table};
+use crate::core::{
+    check_in_scanned, ApiRequest, ApiResponse, AppMode, AttendanceBook, AttendanceStatistics,
+    AttendanceStatus, BoardChoice, CellSpan, GridText, Position, RollCall, RollCallAnswer, Roster,
+    ScanOutcome, SelfCheckIn, SelfCheckInError, SelfCheckInOutcome, Subject, Table, Zoom,
+};
 use crate::ui::cell_edit_dialog::{CellEditDialog, CellEditDraft};
 use crate::ui::corner_add_button::CornerAddButton;
+use crate::ui::seat_canvas::{CanvasScene, SeatCanvas, CELL_GAP};
 use crate::ui::status_dialog::StatusDialog;
 use crate::ui::table_cell::TableCell;
+use crate::ui::table_header::TableHeader;
+use crate::ui::ui_styles::set_board_font_scale;
 
 const CELL_WIDTH_HEIGHT_RATIO: f32 = 2.0;
+/// Cell width at zoom 1.0; the height follows `CELL_WIDTH_HEIGHT_RATIO`.
+const BASE_CELL_WIDTH: f64 = 96.0;
 
 const CLASS_GRID: &str = "table-grid";
 const CLASS_SELECTED: &str = "selected";
+const CLASS_HIGHLIGHTED: &str = "highlighted";
 const CLASS_BOARD: &str = "table-board";
 
 type StatusChangedCallback = Rc<dyn Fn(AttendanceStatistics) + 'static>;
 type TableExportedCallback = Rc<dyn Fn(Table) + 'static>;
+type ZoomChangedCallback = Rc<dyn Fn(Zoom) + 'static>;
 
 #[derive(Clone)]
 struct CellWidgets {
-    position: Position,
+    /// Shared with the click handler, so the cell can move when a row or column before
+    /// it is removed.
+    position: Rc<Cell<Position>>,
     container: GtkBox,
     surface: Label,
+    /// What the cell last showed, so unchanged cells are skipped when re-rendering.
+    rendered: Option<RenderedCell>,
+}
+
+#[derive(Clone, PartialEq)]
+struct RenderedCell {
+    mode: AppMode,
+    /// The position and labels `seat` was built from, compared without formatting it.
+    position: Position,
+    row_label: Option<String>,
+    column_label: Option<String>,
+    seat: String,
+    note: Option<String>,
+    subject: Option<Subject>,
+    status: Option<AttendanceStatus>,
+}
+
+impl RenderedCell {
+    fn has_seat_label(&self, table: &Table, position: Position) -> bool {
+        self.position == position
+            && self.row_label.as_deref() == table.row_label(position.y)
+            && self.column_label.as_deref() == table.column_label(position.x)
+    }
+}
+
+/// The drawn board and the boxes holding its row and column headers.
+#[derive(Clone)]
+struct CanvasBoard {
+    canvas: SeatCanvas,
+    row_headers: GtkBox,
+    column_headers: GtkBox,
+}
+
+/// Cells waiting for the next `render_changed_cells`.
+#[derive(Default)]
+struct DirtyCells {
+    /// Everything, headers and edit controls included, e.g. after a mode or layout change.
+    all: bool,
+    positions: Vec<Position>,
+}
+
+/// A row or column of the grid.
+#[derive(Debug, Clone, Copy)]
+enum Line {
+    Row,
+    Column,
 }
 
 struct ViewState {
     mode: AppMode,
-    selected_surface: Option<Label>,
+    /// Edit-mode selection as `(anchor, focus)`; both corners are inclusive.
+    selection: Option<(Position, Position)>,
+    /// Seat outlined by the roll call or random picker.
+    highlighted: Option<Position>,
     board: Option<AspectFrame>,
+    grid: Option<Grid>,
+    board_choice: BoardChoice,
+    /// Set while the cells are drawn instead of being widgets; `cells` is empty then.
+    canvas: Option<CanvasBoard>,
+    /// Seat the drawn board's keyboard focus is on, moved with the arrow keys.
+    canvas_focus: Option<Position>,
+    zoom: Zoom,
     table: Table,
     attendance: AttendanceBook,
     cells: Vec<CellWidgets>,
+    /// Index into `cells` by position, rebuilt whenever everything is re-rendered.
+    cell_index: HashMap<Position, usize>,
+    dirty: DirtyCells,
+    row_headers: Vec<TableHeader>,
+    column_headers: Vec<TableHeader>,
     row_action_buttons: Vec<Widget>,
     column_action_buttons: Vec<Widget>,
     on_status_change: Vec<StatusChangedCallback>,
     on_table_exported: Vec<TableExportedCallback>,
+    on_zoom_change: Vec<ZoomChangedCallback>,
 }
 
 impl ViewState {
@@ -47,28 +131,59 @@ impl ViewState {
 
         Self {
             mode: AppMode::default(),
-            selected_surface: None,
+            selection: None,
+            highlighted: None,
             board: None,
+            grid: None,
+            board_choice: BoardChoice::default(),
+            canvas: None,
+            canvas_focus: None,
+            zoom: Zoom::default(),
             table,
             attendance,
             cells: Vec::new(),
+            cell_index: HashMap::new(),
+            dirty: DirtyCells::default(),
+            row_headers: Vec::new(),
+            column_headers: Vec::new(),
             row_action_buttons: Vec::new(),
             column_action_buttons: Vec::new(),
             on_status_change: Vec::new(),
             on_table_exported: Vec::new(),
+            on_zoom_change: Vec::new(),
         }
     }
+
+    /// Queues the cell at `position` for re-rendering.
+    fn mark_dirty(&mut self, position: Position) {
+        if !self.dirty.all {
+            self.dirty.positions.push(position);
+        }
+    }
+
+    /// Queues every cell and header, for changes that are not tied to single seats.
+    fn mark_all_dirty(&mut self) {
+        self.dirty = DirtyCells {
+            all: true,
+            positions: Vec::new(),
+        };
+    }
 }
 
+/// The seating board, scrollable once zoomed past the window size.
+///
+/// Ctrl+scroll and pinch zoom the board, and dragging with the middle button pans it.
+/// Cells are widgets of their own, or painted by a [`SeatCanvas`] for large layouts,
+/// depending on the [`BoardChoice`].
 pub struct TableView {
-    root: AspectFrame,
+    root: ScrolledWindow,
     state: Rc<RefCell<ViewState>>,
 }
 
 impl TableView {
     pub fn new(table: &Table) -> Self {
         let state = Rc::new(RefCell::new(ViewState::new(table)));
-        let root = AspectFrame::builder()
+        let board = AspectFrame::builder()
             .ratio(Self::table_ratio(table))
             .hexpand(true)
             .vexpand(true)
@@ -76,17 +191,25 @@ impl TableView {
             .valign(Align::Fill)
             .obey_child(false)
             .build();
-        root.add_css_class(CLASS_BOARD);
-        root.set_xalign(0.5);
-        root.set_yalign(0.5);
+        board.add_css_class(CLASS_BOARD);
+        board.set_xalign(0.5);
+        board.set_yalign(0.5);
 
         {
             let mut view_state = state.borrow_mut();
-            view_state.board = Some(root.clone());
+            view_state.board = Some(board.clone());
         }
 
         let grid = Self::build_grid(table, Rc::clone(&state));
-        root.set_child(Some(&grid));
+        board.set_child(Some(&grid));
+
+        let root = ScrolledWindow::builder()
+            .hexpand(true)
+            .vexpand(true)
+            .child(&board)
+            .build();
+        Self::apply_zoom(&state.borrow(), &root);
+        Self::connect_zoom_gestures(&root, &state);
 
         Self { root, state }
     }
@@ -107,10 +230,50 @@ impl TableView {
         self.state.borrow_mut().on_table_exported.push(callback);
     }
 
-    pub fn widget(&self) -> &AspectFrame {
+    pub fn widget(&self) -> &ScrolledWindow {
         &self.root
     }
 
+    pub fn connect_zoom_changed<F>(&self, callback: F)
+    where
+        F: Fn(Zoom) + 'static,
+    {
+        let callback: ZoomChangedCallback = Rc::new(callback);
+        self.state.borrow_mut().on_zoom_change.push(callback);
+    }
+
+    /// Switches between widget cells and the drawn board, rebuilding it when that changes.
+    pub fn set_board(&self, choice: BoardChoice) {
+        let rebuild = {
+            let Ok(mut state) = self.state.try_borrow_mut() else {
+                return;
+            };
+            state.board_choice = choice;
+            choice.draws_canvas(&state.table) != state.canvas.is_some()
+        };
+        if rebuild {
+            Self::rebuild_grid(&self.state);
+        }
+    }
+
+    pub fn zoom(&self) -> Zoom {
+        self.state.borrow().zoom
+    }
+
+    pub fn set_zoom(&self, zoom: Zoom) {
+        Self::change_zoom(&self.state, &self.root, zoom);
+    }
+
+    pub fn zoom_in(&self) {
+        let scale = Self::current_scale(&self.state.borrow());
+        self.set_zoom(Zoom::zoomed_in(scale));
+    }
+
+    pub fn zoom_out(&self) {
+        let scale = Self::current_scale(&self.state.borrow());
+        self.set_zoom(Zoom::zoomed_out(scale));
+    }
+
     pub fn set_mode(&self, mode: AppMode) {
         let mut statistics_to_emit = None;
         let mut table_to_emit: Option<(Table, Vec<TableExportedCallback>)> = None;
@@ -124,11 +287,11 @@ impl TableView {
                 return;
             }
             state.mode = mode;
+            state.mark_all_dirty();
 
             if mode != AppMode::Edit {
-                if let Some(previous) = state.selected_surface.take() {
-                    previous.remove_css_class(CLASS_SELECTED);
-                }
+                state.selection = None;
+                Self::apply_selection_classes(&state);
             }
 
             if previous_mode == AppMode::Edit && mode != AppMode::Edit {
@@ -139,7 +302,7 @@ impl TableView {
             }
         }
 
-        Self::render_all_cells(&self.state);
+        Self::render_changed_cells(&self.state);
 
         if let Some(statistics) = statistics_to_emit {
             Self::emit_status_changed(&self.state, statistics);
@@ -152,16 +315,439 @@ impl TableView {
         }
     }
 
+    /// Swaps in a new layout, keeping attendance for positions that are still active seats.
+    ///
+    /// The grid is only rebuilt when the shape changed; otherwise just the changed cells
+    /// are re-rendered.
+    pub fn replace_table(&self, table: &Table) {
+        let (statistics, same_shape) = {
+            let Ok(mut state) = self.state.try_borrow_mut() else {
+                return;
+            };
+            let previous = std::mem::replace(&mut state.table, table.clone());
+            state.attendance.reconcile_with_table(table);
+            state.mark_all_dirty();
+            let same_shape = previous.row_count() == table.row_count()
+                && previous.column_count() == table.column_count()
+                && table
+                    .iter_positions()
+                    .all(|position| previous.span_at(position) == table.span_at(position));
+            (state.attendance.statistics(table), same_shape)
+        };
+
+        if same_shape {
+            Self::render_changed_cells(&self.state);
+        } else {
+            Self::rebuild_grid(&self.state);
+        }
+        Self::emit_status_changed(&self.state, statistics);
+    }
+
+    /// Snapshot of the layout currently shown, including unsaved edit-mode changes.
+    pub fn table(&self) -> Table {
+        self.state.borrow().table.clone()
+    }
+
+    /// Snapshot of the current attendance statuses.
+    pub fn attendance(&self) -> AttendanceBook {
+        self.state.borrow().attendance.clone()
+    }
+
+    pub fn mode(&self) -> AppMode {
+        self.state.borrow().mode
+    }
+
+    /// Pastes tab-separated text at the top-left corner of the edit selection.
+    ///
+    /// Falls back to the first cell when nothing is selected. The table grows to fit the
+    /// pasted grid, and the pasted area becomes the new selection.
+    pub fn paste_grid_text(&self, text: &str) -> bool {
+        let grid = GridText::parse(text);
+        let (statistics, selection) = {
+            let Ok(mut state) = self.state.try_borrow_mut() else {
+                return false;
+            };
+            if state.mode != AppMode::Edit {
+                return false;
+            }
+
+            let origin = state
+                .selection
+                .map_or(Position { x: 0, y: 0 }, |(anchor, focus)| Position {
+                    x: anchor.x.min(focus.x),
+                    y: anchor.y.min(focus.y),
+                });
+            if !state.table.paste_grid(origin, &grid) {
+                return false;
+            }
+
+            let table = state.table.clone();
+            state.attendance.reconcile_with_table(&table);
+            let end = Position {
+                x: origin.x + grid.column_count().saturating_sub(1),
+                y: origin.y + grid.row_count().saturating_sub(1),
+            };
+            (state.attendance.statistics(&table), (origin, end))
+        };
+
+        Self::rebuild_grid(&self.state);
+        {
+            let mut state = self.state.borrow_mut();
+            state.selection = Some(selection);
+            Self::apply_selection_classes(&state);
+        }
+        Self::emit_status_changed(&self.state, statistics);
+        true
+    }
+
+    /// Merges the edit selection into one cell.
+    pub fn merge_selection(&self) -> bool {
+        self.edit_selection(|table, anchor, focus| table.merge_cells(anchor, focus))
+    }
+
+    /// Splits the merged cell under the selection focus back into single cells.
+    pub fn split_selection(&self) -> bool {
+        self.edit_selection(|table, _, focus| table.split_cell(focus))
+    }
+
+    fn edit_selection<F>(&self, edit: F) -> bool
+    where
+        F: FnOnce(&mut Table, Position, Position) -> bool,
+    {
+        let statistics = {
+            let Ok(mut state) = self.state.try_borrow_mut() else {
+                return false;
+            };
+            let Some((anchor, focus)) = state.selection else {
+                return false;
+            };
+            if state.mode != AppMode::Edit || !edit(&mut state.table, anchor, focus) {
+                return false;
+            }
+
+            let table = state.table.clone();
+            state.attendance.reconcile_with_table(&table);
+            state.attendance.statistics(&table)
+        };
+
+        Self::rebuild_grid(&self.state);
+        Self::emit_status_changed(&self.state, statistics);
+        true
+    }
+
+    /// Exports the current edit selection as tab-separated text.
+    pub fn selection_grid_text(&self) -> Option<String> {
+        let state = self.state.borrow();
+        let (anchor, focus) = state.selection?;
+        Some(GridText::from_table(&state.table, anchor, focus).to_tsv())
+    }
+
+    /// Answers a REST API request against the live state, re-rendering on changes.
+    pub fn handle_api_request(&self, request: &ApiRequest) -> ApiResponse {
+        let (response, statistics) = {
+            let Ok(mut state) = self.state.try_borrow_mut() else {
+                return ApiResponse::error(503, "view is busy, try again");
+            };
+            let table = state.table.clone();
+            let (response, changed) = request.execute(&table, &mut state.attendance);
+            if let (true, ApiRequest::SetStatus { position, .. }) = (changed, request) {
+                state.mark_dirty(*position);
+            }
+            (
+                response,
+                changed.then(|| state.attendance.statistics(&table)),
+            )
+        };
+
+        if let Some(statistics) = statistics {
+            Self::render_changed_cells(&self.state);
+            Self::emit_status_changed(&self.state, statistics);
+        }
+        response
+    }
+
+    /// Starts attendance over: every active seat back to `Unchecked`, notes and check-in
+    /// times dropped.
+    pub fn reset_attendance(&self) {
+        let statistics = {
+            let state = &mut *self.state.borrow_mut();
+            state.attendance = AttendanceBook::new(&state.table);
+            state.mark_all_dirty();
+            state.attendance.statistics(&state.table)
+        };
+        Self::render_changed_cells(&self.state);
+        Self::emit_status_changed(&self.state, statistics);
+    }
+
+    /// Applies a student's self check-in submission to the live state.
+    pub fn self_check_in(
+        &self,
+        gate: &mut SelfCheckIn,
+        client: IpAddr,
+        identity: &str,
+        credential: &str,
+    ) -> Result<SelfCheckInOutcome, SelfCheckInError> {
+        let (outcome, statistics) = {
+            let Ok(mut state) = self.state.try_borrow_mut() else {
+                return Err(SelfCheckInError::Busy);
+            };
+            let table = state.table.clone();
+            let outcome =
+                gate.check_in(&table, &mut state.attendance, client, identity, credential)?;
+            if !outcome.already_checked {
+                state.mark_dirty(outcome.position);
+            }
+            let statistics =
+                (!outcome.already_checked).then(|| state.attendance.statistics(&table));
+            (outcome, statistics)
+        };
+
+        if let Some(statistics) = statistics {
+            Self::render_changed_cells(&self.state);
+            Self::emit_status_changed(&self.state, statistics);
+        }
+        Ok(outcome)
+    }
+
+    /// Checks in the seat for a scanned card code. `None` while the view is busy.
+    pub fn check_in_scanned(&self, roster: &Roster, code: &str) -> Option<ScanOutcome> {
+        let (outcome, statistics) = {
+            let Ok(mut state) = self.state.try_borrow_mut() else {
+                return None;
+            };
+            let table = state.table.clone();
+            let outcome = check_in_scanned(&table, &mut state.attendance, roster, code);
+            if let ScanOutcome::Checked { position, .. } = &outcome {
+                state.mark_dirty(*position);
+            }
+            let statistics = matches!(outcome, ScanOutcome::Checked { .. })
+                .then(|| state.attendance.statistics(&table));
+            (outcome, statistics)
+        };
+
+        if let Some(statistics) = statistics {
+            Self::render_changed_cells(&self.state);
+            Self::emit_status_changed(&self.state, statistics);
+        }
+        Some(outcome)
+    }
+
+    /// Records a roll-call answer for the current seat and advances the roll call.
+    ///
+    /// Returns `false` without advancing while the view is busy.
+    pub fn answer_roll_call(&self, roll_call: &mut RollCall, answer: RollCallAnswer) -> bool {
+        let statistics = {
+            let Ok(mut state) = self.state.try_borrow_mut() else {
+                return false;
+            };
+            let table = state.table.clone();
+            let position = roll_call.current().map(|(position, _)| position);
+            if !roll_call.answer(&table, &mut state.attendance, answer) {
+                return true;
+            }
+            if let Some(position) = position {
+                state.mark_dirty(position);
+            }
+            state.attendance.statistics(&table)
+        };
+
+        Self::render_changed_cells(&self.state);
+        Self::emit_status_changed(&self.state, statistics);
+        true
+    }
+
+    pub fn status_at(&self, position: Position) -> Option<AttendanceStatus> {
+        self.state.borrow().attendance.status_at(position)
+    }
+
+    /// Outlines one cell, e.g. the seat currently being called.
+    pub fn set_highlighted(&self, highlighted: Option<Position>) {
+        let mut state = self.state.borrow_mut();
+        state.highlighted = highlighted;
+        for cell in &state.cells {
+            if Some(cell.position.get()) == highlighted {
+                cell.surface.add_css_class(CLASS_HIGHLIGHTED);
+            } else {
+                cell.surface.remove_css_class(CLASS_HIGHLIGHTED);
+            }
+        }
+        if let Some(board) = &state.canvas {
+            board.canvas.queue_draw();
+        }
+    }
+
+    pub fn highlighted(&self) -> Option<Position> {
+        self.state.borrow().highlighted
+    }
+
     pub fn get_statistics(&self) -> AttendanceStatistics {
         let state = self.state.borrow();
         state.attendance.statistics(&state.table)
     }
 
+    /// Statistics for each group tagged in the table, sorted by group name.
+    pub fn group_statistics(&self) -> Vec<(String, AttendanceStatistics)> {
+        let state = self.state.borrow();
+        state.attendance.group_statistics(&state.table)
+    }
+
     pub fn build_statistics_export_text_zh(&self, time: &SystemTime) -> String {
         let state = self.state.borrow();
         state.attendance.build_export_text_zh(&state.table, time)
     }
 
+    pub fn build_statistics_export_text(&self, time: &SystemTime, template: &str) -> String {
+        let state = self.state.borrow();
+        state
+            .attendance
+            .build_export_text(&state.table, time, template)
+    }
+
+    fn change_zoom(state: &Rc<RefCell<ViewState>>, scroller: &ScrolledWindow, zoom: Zoom) {
+        let callbacks = {
+            let Ok(mut view_state) = state.try_borrow_mut() else {
+                return;
+            };
+            if view_state.zoom == zoom {
+                return;
+            }
+            view_state.zoom = zoom;
+            Self::apply_zoom(&view_state, scroller);
+            view_state.on_zoom_change.clone()
+        };
+        for callback in callbacks {
+            callback(zoom);
+        }
+    }
+
+    /// Scale the board is drawn at, measured from its allocation in `Fit` mode.
+    fn current_scale(view_state: &ViewState) -> f64 {
+        match view_state.zoom {
+            Zoom::Scale(scale) => scale,
+            Zoom::Fit => {
+                let width = view_state
+                    .board
+                    .as_ref()
+                    .and_then(|board| board.child())
+                    .map_or(0, |grid| grid.width());
+                let natural = f64::from(view_state.table.column_count().max(1)) * BASE_CELL_WIDTH;
+                if width > 0 {
+                    f64::from(width) / natural
+                } else {
+                    1.0
+                }
+            }
+        }
+    }
+
+    fn apply_zoom(view_state: &ViewState, scroller: &ScrolledWindow) {
+        match view_state.zoom {
+            Zoom::Fit => {
+                scroller.set_policy(PolicyType::Never, PolicyType::Never);
+                set_board_font_scale(1.0);
+            }
+            Zoom::Scale(scale) => {
+                scroller.set_policy(PolicyType::Automatic, PolicyType::Automatic);
+                set_board_font_scale(scale);
+            }
+        }
+        Self::apply_board_size(view_state);
+    }
+
+    /// Sizes the board for the zoom level; `Fit` leaves it to the window.
+    fn apply_board_size(view_state: &ViewState) {
+        let Some(board) = view_state.board.as_ref() else {
+            return;
+        };
+        match view_state.zoom {
+            Zoom::Fit => board.set_size_request(-1, -1),
+            Zoom::Scale(scale) => {
+                let cell_width = BASE_CELL_WIDTH * scale;
+                let cell_height = cell_width / f64::from(CELL_WIDTH_HEIGHT_RATIO);
+                let width = f64::from(view_state.table.column_count()) * cell_width;
+                let height = f64::from(view_state.table.row_count()) * cell_height;
+                board.set_size_request(width.round() as i32, height.round() as i32);
+            }
+        }
+    }
+
+    fn connect_zoom_gestures(scroller: &ScrolledWindow, state: &Rc<RefCell<ViewState>>) {
+        let scroll = EventControllerScroll::new(EventControllerScrollFlags::VERTICAL);
+        scroll.set_propagation_phase(PropagationPhase::Capture);
+        {
+            let state = Rc::clone(state);
+            let weak_scroller = scroller.downgrade();
+            scroll.connect_scroll(move |controller, _, dy| {
+                let Some(scroller) = weak_scroller.upgrade() else {
+                    return glib::Propagation::Proceed;
+                };
+                if !controller
+                    .current_event_state()
+                    .contains(gdk::ModifierType::CONTROL_MASK)
+                {
+                    return glib::Propagation::Proceed;
+                }
+                let scale = Self::current_scale(&state.borrow());
+                let zoom = if dy < 0.0 {
+                    Zoom::zoomed_in(scale)
+                } else {
+                    Zoom::zoomed_out(scale)
+                };
+                Self::change_zoom(&state, &scroller, zoom);
+                glib::Propagation::Stop
+            });
+        }
+        scroller.add_controller(scroll);
+
+        let pinch = GestureZoom::new();
+        let pinch_start = Rc::new(Cell::new(1.0));
+        {
+            let state = Rc::clone(state);
+            let pinch_start = Rc::clone(&pinch_start);
+            pinch.connect_begin(move |_, _| {
+                pinch_start.set(Self::current_scale(&state.borrow()));
+            });
+        }
+        {
+            let state = Rc::clone(state);
+            let weak_scroller = scroller.downgrade();
+            pinch.connect_scale_changed(move |_, factor| {
+                if let Some(scroller) = weak_scroller.upgrade() {
+                    let zoom = Zoom::scaled_by(pinch_start.get(), factor);
+                    Self::change_zoom(&state, &scroller, zoom);
+                }
+            });
+        }
+        scroller.add_controller(pinch);
+
+        let pan = GestureDrag::new();
+        pan.set_button(gdk::BUTTON_MIDDLE);
+        let pan_start = Rc::new(Cell::new((0.0, 0.0)));
+        {
+            let pan_start = Rc::clone(&pan_start);
+            let weak_scroller = scroller.downgrade();
+            pan.connect_drag_begin(move |_, _, _| {
+                if let Some(scroller) = weak_scroller.upgrade() {
+                    pan_start.set((
+                        scroller.hadjustment().value(),
+                        scroller.vadjustment().value(),
+                    ));
+                }
+            });
+        }
+        {
+            let weak_scroller = scroller.downgrade();
+            pan.connect_drag_update(move |_, offset_x, offset_y| {
+                if let Some(scroller) = weak_scroller.upgrade() {
+                    let (start_x, start_y) = pan_start.get();
+                    scroller.hadjustment().set_value(start_x - offset_x);
+                    scroller.vadjustment().set_value(start_y - offset_y);
+                }
+            });
+        }
+        scroller.add_controller(pan);
+    }
+
     fn table_ratio(table: &Table) -> f32 {
         let row_count = table.row_count().max(1) as f32;
         let column_count = table.column_count().max(1) as f32;
@@ -180,95 +766,55 @@ impl TableView {
         grid.set_valign(Align::Fill);
         grid.add_css_class(CLASS_GRID);
 
-        {
+        let canvas = {
             let mut view_state = state.borrow_mut();
+            view_state.grid = Some(grid.clone());
             view_state.cells.clear();
+            view_state.mark_all_dirty();
+            view_state.row_headers.clear();
+            view_state.column_headers.clear();
             view_state.row_action_buttons.clear();
             view_state.column_action_buttons.clear();
-        }
-
-        for y in 0..table.row_count() {
-            for x in 0..table.column_count() {
-                let position = Position { x, y };
-                let cell = TableCell::new();
-                Self::connect_cell_events(
-                    cell.container(),
-                    cell.surface(),
-                    position,
-                    Rc::clone(&state),
-                );
-                grid.attach(cell.container(), x as i32 + 1, y as i32 + 1, 1, 1);
+            view_state.canvas = view_state
+                .board_choice
+                .draws_canvas(table)
+                .then(|| Self::build_canvas(&state));
+            view_state.canvas.clone()
+        };
 
-                state.borrow_mut().cells.push(CellWidgets {
-                    position,
-                    container: cell.container().clone(),
-                    surface: cell.surface().clone(),
-                });
+        match canvas {
+            Some(board) => {
+                grid.attach(&board.column_headers, 1, 0, 1, 1);
+                grid.attach(&board.row_headers, 0, 1, 1, 1);
+                grid.attach(board.canvas.widget(), 1, 1, 1, 1);
+            }
+            None => {
+                for y in 0..table.row_count() {
+                    for x in 0..table.column_count() {
+                        let position = Position { x, y };
+                        if !table.is_covered(position) {
+                            Self::attach_cell(&grid, &state, position, table.span_at(position));
+                        }
+                    }
+                }
             }
         }
-
         for y in 0..table.row_count() {
-            let button = Self::build_action_button("−");
-            Self::connect_remove_row(&button, y, Rc::clone(&state));
-            grid.attach(&button, 0, y as i32 + 1, 1, 1);
-            state.borrow_mut().row_action_buttons.push(button.upcast());
+            Self::attach_header(&grid, &state, Line::Row, y);
         }
-
         for x in 0..table.column_count() {
-            let button = Self::build_action_button("−");
-            Self::connect_remove_column(&button, x, Rc::clone(&state));
-            grid.attach(&button, x as i32 + 1, 0, 1, 1);
-            state
-                .borrow_mut()
-                .column_action_buttons
-                .push(button.upcast());
+            Self::attach_header(&grid, &state, Line::Column, x);
         }
 
         let corner_button = CornerAddButton::new();
         corner_button.connect_split(
             {
                 let state = Rc::clone(&state);
-                move || {
-                    let statistics = {
-                        let Ok(mut view_state) = state.try_borrow_mut() else {
-                            return;
-                        };
-                        if view_state.mode != AppMode::Edit {
-                            None
-                        } else {
-                            view_state.table.add_row();
-                            let table = view_state.table.clone();
-                            view_state.attendance.reconcile_with_table(&table);
-                            Some(view_state.attendance.statistics(&table))
-                        }
-                    };
-                    if let Some(statistics) = statistics {
-                        Self::rebuild_grid(&state);
-                        Self::emit_status_changed(&state, statistics);
-                    }
-                }
+                move || Self::append_line(&state, Line::Row)
             },
             {
                 let state = Rc::clone(&state);
-                move || {
-                    let statistics = {
-                        let Ok(mut view_state) = state.try_borrow_mut() else {
-                            return;
-                        };
-                        if view_state.mode != AppMode::Edit {
-                            None
-                        } else {
-                            view_state.table.add_column();
-                            let table = view_state.table.clone();
-                            view_state.attendance.reconcile_with_table(&table);
-                            Some(view_state.attendance.statistics(&table))
-                        }
-                    };
-                    if let Some(statistics) = statistics {
-                        Self::rebuild_grid(&state);
-                        Self::emit_status_changed(&state, statistics);
-                    }
-                }
+                move || Self::append_line(&state, Line::Column)
             },
         );
         grid.attach(corner_button.widget(), 0, 0, 1, 1);
@@ -282,94 +828,561 @@ impl TableView {
                 .push(corner_button.widget().clone().upcast());
         }
 
-        Self::render_all_cells(&state);
+        Self::render_changed_cells(&state);
         grid
     }
 
-    fn connect_cell_events(
-        cell: &GtkBox,
-        surface: &Label,
+    /// A drawn board that paints from the view state and forwards clicks like cell widgets.
+    fn build_canvas(state: &Rc<RefCell<ViewState>>) -> CanvasBoard {
+        let canvas = SeatCanvas::new();
+        {
+            let state = Rc::clone(state);
+            let weak_area = canvas.widget().downgrade();
+            canvas.set_draw_func(move |cr, width, height| {
+                let Ok(view_state) = state.try_borrow() else {
+                    return Ok(());
+                };
+                let has_focus = weak_area.upgrade().is_some_and(|area| area.has_focus());
+                let scene = CanvasScene {
+                    table: &view_state.table,
+                    attendance: &view_state.attendance,
+                    mode: view_state.mode,
+                    selection: view_state.selection,
+                    highlighted: view_state.highlighted,
+                    focused: Self::canvas_focus(&view_state).filter(|_| has_focus),
+                    scale: Self::current_scale(&view_state),
+                };
+                SeatCanvas::paint(cr, &scene, width, height)
+            });
+        }
+
+        let click = GestureClick::new();
+        {
+            let state = Rc::clone(state);
+            let weak_area = canvas.widget().downgrade();
+            click.connect_pressed(move |gesture, n_press, x, y| {
+                let Some(area) = weak_area.upgrade() else {
+                    return;
+                };
+                let position = {
+                    let Ok(view_state) = state.try_borrow() else {
+                        return;
+                    };
+                    SeatCanvas::position_at(
+                        &view_state.table,
+                        f64::from(area.width()),
+                        f64::from(area.height()),
+                        x,
+                        y,
+                    )
+                };
+                if let Some(position) = position {
+                    if let Ok(mut view_state) = state.try_borrow_mut() {
+                        view_state.canvas_focus = Some(position);
+                    }
+                    Self::describe_canvas_focus(area.upcast_ref(), &state);
+                    let extend = gesture
+                        .current_event_state()
+                        .contains(gdk::ModifierType::SHIFT_MASK);
+                    Self::press_cell(area.upcast_ref(), position, n_press, extend, &state);
+                }
+            });
+        }
+        canvas.widget().add_controller(click);
+
+        {
+            let state = Rc::clone(state);
+            canvas.set_tooltip_func(move |width, height, x, y| {
+                let view_state = state.try_borrow().ok()?;
+                if view_state.mode != AppMode::CheckIn {
+                    return None;
+                }
+                SeatCanvas::note_at(
+                    &view_state.table,
+                    &view_state.attendance,
+                    width,
+                    height,
+                    x,
+                    y,
+                )
+                .map(str::to_owned)
+            });
+        }
+
+        let keys = EventControllerKey::new();
+        {
+            let state = Rc::clone(state);
+            let weak_area = canvas.widget().downgrade();
+            keys.connect_key_pressed(move |_, key, _, modifiers| {
+                let Some(area) = weak_area.upgrade() else {
+                    return glib::Propagation::Proceed;
+                };
+                if let Some(n_press) = Self::key_press_count(key) {
+                    let focus = state
+                        .try_borrow()
+                        .ok()
+                        .and_then(|view_state| Self::canvas_focus(&view_state));
+                    let Some(position) = focus else {
+                        return glib::Propagation::Proceed;
+                    };
+                    let extend = modifiers.contains(gdk::ModifierType::SHIFT_MASK);
+                    Self::press_cell(area.upcast_ref(), position, n_press, extend, &state);
+                    return glib::Propagation::Stop;
+                }
+                if !Self::move_canvas_focus(&state, key) {
+                    return glib::Propagation::Proceed;
+                }
+                Self::describe_canvas_focus(area.upcast_ref(), &state);
+                area.queue_draw();
+                glib::Propagation::Stop
+            });
+        }
+        canvas.widget().add_controller(keys);
+
+        let focus = EventControllerFocus::new();
+        {
+            let state = Rc::clone(state);
+            let weak_area = canvas.widget().downgrade();
+            focus.connect_enter(move |_| {
+                if let Some(area) = weak_area.upgrade() {
+                    Self::describe_canvas_focus(area.upcast_ref(), &state);
+                    area.queue_draw();
+                }
+            });
+        }
+        {
+            let weak_area = canvas.widget().downgrade();
+            focus.connect_leave(move |_| {
+                if let Some(area) = weak_area.upgrade() {
+                    area.queue_draw();
+                }
+            });
+        }
+        canvas.widget().add_controller(focus);
+
+        let header_box = |orientation| {
+            let headers = GtkBox::new(orientation, CELL_GAP as i32);
+            headers.set_homogeneous(true);
+            headers
+        };
+        CanvasBoard {
+            canvas,
+            row_headers: header_box(Orientation::Vertical),
+            column_headers: header_box(Orientation::Horizontal),
+        }
+    }
+
+    /// The drawn board's focused seat, starting from the top-left one.
+    fn canvas_focus(view_state: &ViewState) -> Option<Position> {
+        let table = &view_state.table;
+        let focus = view_state
+            .canvas_focus
+            .filter(|position| table.contains(*position))
+            .unwrap_or(Position { x: 0, y: 0 });
+        table.contains(focus).then(|| table.cell_origin(focus))
+    }
+
+    /// Moves the drawn board's focus for an arrow key, stepping over merged cells.
+    /// Returns false for other keys and at the edge of the board.
+    fn move_canvas_focus(state: &Rc<RefCell<ViewState>>, key: gdk::Key) -> bool {
+        let Ok(mut view_state) = state.try_borrow_mut() else {
+            return false;
+        };
+        let Some(origin) = Self::canvas_focus(&view_state) else {
+            return false;
+        };
+        let span = view_state.table.span_at(origin);
+        let next = match key {
+            gdk::Key::Left | gdk::Key::KP_Left => {
+                origin.x.checked_sub(1).map(|x| Position { x, ..origin })
+            }
+            gdk::Key::Right | gdk::Key::KP_Right => Some(Position {
+                x: origin.x + span.columns,
+                ..origin
+            }),
+            gdk::Key::Up | gdk::Key::KP_Up => {
+                origin.y.checked_sub(1).map(|y| Position { y, ..origin })
+            }
+            gdk::Key::Down | gdk::Key::KP_Down => Some(Position {
+                y: origin.y + span.rows,
+                ..origin
+            }),
+            _ => None,
+        };
+        let Some(next) = next.filter(|next| view_state.table.contains(*next)) else {
+            return false;
+        };
+        let next = view_state.table.cell_origin(next);
+        view_state.canvas_focus = Some(next);
+        true
+    }
+
+    /// Names the drawn board after its focused seat, so screen readers announce each move.
+    fn describe_canvas_focus(area: &Widget, state: &Rc<RefCell<ViewState>>) {
+        let Ok(view_state) = state.try_borrow() else {
+            return;
+        };
+        let name = match Self::canvas_focus(&view_state) {
+            Some(position) => Self::seat_description(&view_state, position),
+            None => String::from("Seating board"),
+        };
+        area.update_property(&[accessible::Property::Label(&name)]);
+    }
+
+    /// How screen readers and the status dialog refer to a seat.
+    fn seat_description(view_state: &ViewState, position: Position) -> String {
+        TableCell::accessible_name(
+            &view_state.table.seat_label(position),
+            view_state.attendance.note_at(position),
+            view_state.mode,
+            view_state.table.subject_at(position),
+            view_state.attendance.status_at(position),
+        )
+    }
+
+    fn attach_cell(
+        grid: &Grid,
+        state: &Rc<RefCell<ViewState>>,
         position: Position,
-        state: Rc<RefCell<ViewState>>,
+        span: CellSpan,
     ) {
-        let click = GestureClick::new();
-        let weak_cell = cell.downgrade();
-        let weak_surface = surface.downgrade();
+        let position_slot = Rc::new(Cell::new(position));
+        let cell = TableCell::new();
+        Self::connect_cell_events(
+            cell.container(),
+            Rc::clone(&position_slot),
+            Rc::clone(state),
+        );
+        grid.attach(
+            cell.container(),
+            position.x as i32 + 1,
+            position.y as i32 + 1,
+            span.columns as i32,
+            span.rows as i32,
+        );
+
+        state.borrow_mut().cells.push(CellWidgets {
+            position: position_slot,
+            container: cell.container().clone(),
+            surface: cell.surface().clone(),
+            rendered: None,
+        });
+    }
+
+    fn attach_header(grid: &Grid, state: &Rc<RefCell<ViewState>>, line: Line, index: u32) {
+        let header = TableHeader::new(index, Self::build_action_button("−"));
+        {
+            let state = Rc::clone(state);
+            header.connect_remove(move |index| Self::remove_line(&state, line, index));
+        }
+        {
+            let state = Rc::clone(state);
+            header.connect_label_changed(move |index, label| {
+                if let Ok(mut view_state) = state.try_borrow_mut() {
+                    if view_state.mode == AppMode::Edit {
+                        match line {
+                            Line::Row => view_state.table.set_row_label(index, label),
+                            Line::Column => view_state.table.set_column_label(index, label),
+                        };
+                    }
+                }
+            });
+        }
+
+        let mut view_state = state.borrow_mut();
+        match (line, &view_state.canvas) {
+            (Line::Row, Some(board)) => board.row_headers.append(header.widget()),
+            (Line::Column, Some(board)) => board.column_headers.append(header.widget()),
+            (Line::Row, None) => grid.attach(header.widget(), 0, index as i32 + 1, 1, 1),
+            (Line::Column, None) => grid.attach(header.widget(), index as i32 + 1, 0, 1, 1),
+        }
+        match line {
+            Line::Row => view_state.row_headers.push(header),
+            Line::Column => view_state.column_headers.push(header),
+        }
+    }
+
+    /// Adds a row or column at the end, attaching widgets only for the new line.
+    fn append_line(state: &Rc<RefCell<ViewState>>, line: Line) {
+        let (statistics, grid, table, drawn) = {
+            let Ok(mut view_state) = state.try_borrow_mut() else {
+                return;
+            };
+            if view_state.mode != AppMode::Edit {
+                return;
+            }
+            match line {
+                Line::Row => view_state.table.add_row(),
+                Line::Column => view_state.table.add_column(),
+            }
+            view_state.mark_all_dirty();
+            let table = view_state.table.clone();
+            view_state.attendance.reconcile_with_table(&table);
+            (
+                view_state.attendance.statistics(&table),
+                view_state.grid.clone(),
+                table,
+                view_state.canvas.is_some(),
+            )
+        };
+        let Some(grid) = grid else {
+            return;
+        };
 
-        click.connect_pressed(move |_, n_press, _, _| {
-            let Some(cell) = weak_cell.upgrade() else {
+        let (index, new_cells) = match line {
+            Line::Row => {
+                let y = table.row_count() - 1;
+                (
+                    y,
+                    (0..table.column_count())
+                        .map(|x| Position { x, y })
+                        .collect::<Vec<_>>(),
+                )
+            }
+            Line::Column => {
+                let x = table.column_count() - 1;
+                (
+                    x,
+                    (0..table.row_count()).map(|y| Position { x, y }).collect(),
+                )
+            }
+        };
+        // The drawn board picks up new cells on its next paint.
+        if !drawn {
+            for position in new_cells {
+                Self::attach_cell(&grid, state, position, CellSpan::SINGLE);
+            }
+        }
+        Self::attach_header(&grid, state, line, index);
+
+        Self::refresh_board_shape(state);
+        Self::render_changed_cells(state);
+        Self::emit_status_changed(state, statistics);
+    }
+
+    /// Removes a row or column in place: its widgets go, and the cells and headers
+    /// after it move back by one.
+    ///
+    /// Merged cells crossing the line shrink, which `Grid::remove_row` and
+    /// `Grid::remove_column` mirror for the attached widgets.
+    fn remove_line(state: &Rc<RefCell<ViewState>>, line: Line, index: u32) {
+        let (statistics, grid, canvas, removed_header) = {
+            let Ok(mut guard) = state.try_borrow_mut() else {
                 return;
             };
-            let Some(surface) = weak_surface.upgrade() else {
+            let view_state = &mut *guard;
+            if view_state.mode != AppMode::Edit {
                 return;
+            }
+            let spans: Vec<CellSpan> = view_state
+                .cells
+                .iter()
+                .map(|cell| view_state.table.span_at(cell.position.get()))
+                .collect();
+            let removed = match line {
+                Line::Row => view_state.table.remove_row(index),
+                Line::Column => view_state.table.remove_column(index),
             };
+            if !removed {
+                return;
+            }
 
-            let mode = {
-                let Ok(view_state) = state.try_borrow() else {
+            let cells = std::mem::take(&mut view_state.cells);
+            view_state.cells = cells
+                .into_iter()
+                .zip(spans)
+                .filter_map(|(cell, span)| {
+                    let position = cell.position.get();
+                    let (coordinate, length) = match line {
+                        Line::Row => (position.y, span.rows),
+                        Line::Column => (position.x, span.columns),
+                    };
+                    match coordinate.cmp(&index) {
+                        Ordering::Less => {}
+                        Ordering::Equal if length > 1 => {}
+                        Ordering::Equal => return None,
+                        Ordering::Greater => cell.position.set(match line {
+                            Line::Row => Position {
+                                x: position.x,
+                                y: position.y - 1,
+                            },
+                            Line::Column => Position {
+                                x: position.x - 1,
+                                y: position.y,
+                            },
+                        }),
+                    }
+                    Some(cell)
+                })
+                .collect();
+
+            let headers = match line {
+                Line::Row => &mut view_state.row_headers,
+                Line::Column => &mut view_state.column_headers,
+            };
+            let removed_header = headers.remove(index as usize);
+            for header in &headers[index as usize..] {
+                header.set_index(header.index() - 1);
+            }
+
+            view_state.selection = None;
+            Self::apply_selection_classes(view_state);
+            view_state.mark_all_dirty();
+            let table = view_state.table.clone();
+            view_state.attendance.reconcile_with_table(&table);
+            (
+                view_state.attendance.statistics(&table),
+                view_state.grid.clone(),
+                view_state.canvas.clone(),
+                removed_header,
+            )
+        };
+
+        match (canvas, grid) {
+            (Some(board), _) => match line {
+                Line::Row => board.row_headers.remove(removed_header.widget()),
+                Line::Column => board.column_headers.remove(removed_header.widget()),
+            },
+            (None, Some(grid)) => match line {
+                Line::Row => grid.remove_row(index as i32 + 1),
+                Line::Column => grid.remove_column(index as i32 + 1),
+            },
+            (None, None) => {}
+        }
+        Self::refresh_board_shape(state);
+        Self::render_changed_cells(state);
+        Self::emit_status_changed(state, statistics);
+    }
+
+    /// Keeps the board proportions in step with the row and column counts.
+    fn refresh_board_shape(state: &Rc<RefCell<ViewState>>) {
+        let view_state = state.borrow();
+        if let Some(board) = view_state.board.as_ref() {
+            board.set_ratio(Self::table_ratio(&view_state.table));
+        }
+        Self::apply_board_size(&view_state);
+    }
+
+    fn connect_cell_events(
+        cell: &GtkBox,
+        position: Rc<Cell<Position>>,
+        state: Rc<RefCell<ViewState>>,
+    ) {
+        let click = GestureClick::new();
+        {
+            let weak_cell = cell.downgrade();
+            let position = Rc::clone(&position);
+            let state = Rc::clone(&state);
+            click.connect_pressed(move |gesture, n_press, _, _| {
+                let Some(cell) = weak_cell.upgrade() else {
                     return;
                 };
-                view_state.mode
+                let extend = gesture
+                    .current_event_state()
+                    .contains(gdk::ModifierType::SHIFT_MASK);
+                Self::press_cell(cell.upcast_ref(), position.get(), n_press, extend, &state);
+            });
+        }
+        cell.add_controller(click);
+
+        let keys = EventControllerKey::new();
+        let weak_cell = cell.downgrade();
+        keys.connect_key_pressed(move |_, key, _, modifiers| {
+            let (Some(cell), Some(n_press)) = (weak_cell.upgrade(), Self::key_press_count(key))
+            else {
+                return glib::Propagation::Proceed;
             };
+            let extend = modifiers.contains(gdk::ModifierType::SHIFT_MASK);
+            Self::press_cell(cell.upcast_ref(), position.get(), n_press, extend, &state);
+            glib::Propagation::Stop
+        });
+        cell.add_controller(keys);
+    }
 
-            match mode {
-                AppMode::Edit => {
-                    if n_press == 1 {
-                        Self::select_for_edit(&surface, &state);
-                    }
-                    if n_press == 2 {
-                        Self::open_edit_dialog(&cell, position, Rc::clone(&state));
-                    }
+    /// Keyboard stand-ins for clicks: Space selects like a single click, Enter acts like
+    /// a double click.
+    fn key_press_count(key: gdk::Key) -> Option<i32> {
+        match key {
+            gdk::Key::space | gdk::Key::KP_Space => Some(1),
+            gdk::Key::Return | gdk::Key::KP_Enter | gdk::Key::ISO_Enter => Some(2),
+            _ => None,
+        }
+    }
+
+    /// Selects or edits in edit mode, and asks for a status on double click in check-in
+    /// mode. `parent` is the widget the dialogs belong to.
+    fn press_cell(
+        parent: &Widget,
+        position: Position,
+        n_press: i32,
+        extend: bool,
+        state: &Rc<RefCell<ViewState>>,
+    ) {
+        let mode = {
+            let Ok(view_state) = state.try_borrow() else {
+                return;
+            };
+            view_state.mode
+        };
+
+        match mode {
+            AppMode::Edit => {
+                if n_press == 1 {
+                    Self::select_for_edit(position, extend, state);
                 }
-                AppMode::CheckIn => {
-                    if n_press != 2 {
+                if n_press == 2 {
+                    Self::open_edit_dialog(parent, position, Rc::clone(state));
+                }
+            }
+            AppMode::CheckIn => {
+                if n_press != 2 {
+                    return;
+                }
+                let can_checkin = {
+                    let Ok(view_state) = state.try_borrow() else {
                         return;
-                    }
-                    let can_checkin = {
-                        let Ok(view_state) = state.try_borrow() else {
-                            return;
-                        };
-                        !view_state.table.is_inert(position)
                     };
-                    if can_checkin {
-                        Self::open_status_dialog(&cell, &surface, position, Rc::clone(&state));
-                    }
+                    !view_state.table.is_inert(position)
+                };
+                if can_checkin {
+                    Self::open_status_dialog(parent, position, Rc::clone(state));
                 }
             }
-        });
-
-        cell.add_controller(click);
+        }
     }
 
-    fn open_status_dialog(
-        cell: &GtkBox,
-        surface: &Label,
-        position: Position,
-        state: Rc<RefCell<ViewState>>,
-    ) {
-        StatusDialog::present(cell, surface, move |status, _| {
+    fn open_status_dialog(parent: &Widget, position: Position, state: Rc<RefCell<ViewState>>) {
+        let (seat, note) = {
+            let Ok(view_state) = state.try_borrow() else {
+                return;
+            };
+            (
+                Self::seat_description(&view_state, position),
+                view_state.attendance.note_at(position).map(str::to_owned),
+            )
+        };
+        StatusDialog::present(parent, &seat, note.as_deref(), move |status, note| {
             let statistics = {
                 let Ok(mut view_state) = state.try_borrow_mut() else {
                     return;
                 };
                 let table = view_state.table.clone();
-                if !view_state
+                let status_changed = view_state
                     .attendance
-                    .update_status(&table, position, status)
-                {
-                    None
-                } else {
-                    Some(view_state.attendance.statistics(&table))
-                }
+                    .update_status(&table, position, status);
+                let note_changed = view_state.attendance.set_note(&table, position, note);
+                view_state.mark_dirty(position);
+                (status_changed || note_changed).then(|| view_state.attendance.statistics(&table))
             };
 
             if let Some(statistics) = statistics {
-                Self::render_all_cells(&state);
+                Self::render_changed_cells(&state);
                 Self::emit_status_changed(&state, statistics);
             }
         });
     }
 
-    fn open_edit_dialog(cell: &GtkBox, position: Position, state: Rc<RefCell<ViewState>>) {
+    fn open_edit_dialog(parent: &Widget, position: Position, state: Rc<RefCell<ViewState>>) {
         let initial = {
             let Ok(view_state) = state.try_borrow() else {
                 return;
@@ -377,7 +1390,7 @@ impl TableView {
             CellEditDraft::from_subject(view_state.table.subject_at(position))
         };
 
-        CellEditDialog::present(cell, initial, move |draft| {
+        CellEditDialog::present(parent, initial, move |draft| {
             let statistics = {
                 let Ok(mut view_state) = state.try_borrow_mut() else {
                     return;
@@ -387,69 +1400,47 @@ impl TableView {
                 } else {
                     let table = view_state.table.clone();
                     view_state.attendance.reconcile_with_table(&table);
+                    view_state.mark_dirty(position);
                     Some(view_state.attendance.statistics(&table))
                 }
             };
 
             if let Some(statistics) = statistics {
-                Self::render_all_cells(&state);
+                Self::render_changed_cells(&state);
                 Self::emit_status_changed(&state, statistics);
             }
         });
     }
 
-    fn select_for_edit(surface: &Label, state: &Rc<RefCell<ViewState>>) {
+    /// Selects a single cell, or extends the selection rectangle when `extend` is set.
+    fn select_for_edit(position: Position, extend: bool, state: &Rc<RefCell<ViewState>>) {
         let Ok(mut view_state) = state.try_borrow_mut() else {
             return;
         };
-        if let Some(previous) = view_state.selected_surface.take() {
-            previous.remove_css_class(CLASS_SELECTED);
-        }
-        surface.add_css_class(CLASS_SELECTED);
-        view_state.selected_surface = Some(surface.clone());
-    }
-
-    fn connect_remove_row(button: &Button, row_index: u32, state: Rc<RefCell<ViewState>>) {
-        button.connect_clicked(move |_| {
-            let statistics = {
-                let Ok(mut view_state) = state.try_borrow_mut() else {
-                    return;
-                };
-                if view_state.mode != AppMode::Edit || !view_state.table.remove_row(row_index) {
-                    None
-                } else {
-                    let table = view_state.table.clone();
-                    view_state.attendance.reconcile_with_table(&table);
-                    Some(view_state.attendance.statistics(&table))
-                }
-            };
-            if let Some(statistics) = statistics {
-                Self::rebuild_grid(&state);
-                Self::emit_status_changed(&state, statistics);
-            }
-        });
+        let anchor = match view_state.selection {
+            Some((anchor, _)) if extend => anchor,
+            _ => position,
+        };
+        view_state.selection = Some((anchor, position));
+        Self::apply_selection_classes(&view_state);
     }
 
-    fn connect_remove_column(button: &Button, column_index: u32, state: Rc<RefCell<ViewState>>) {
-        button.connect_clicked(move |_| {
-            let statistics = {
-                let Ok(mut view_state) = state.try_borrow_mut() else {
-                    return;
-                };
-                if view_state.mode != AppMode::Edit || !view_state.table.remove_column(column_index)
-                {
-                    None
-                } else {
-                    let table = view_state.table.clone();
-                    view_state.attendance.reconcile_with_table(&table);
-                    Some(view_state.attendance.statistics(&table))
-                }
-            };
-            if let Some(statistics) = statistics {
-                Self::rebuild_grid(&state);
-                Self::emit_status_changed(&state, statistics);
+    fn apply_selection_classes(view_state: &ViewState) {
+        for cell in &view_state.cells {
+            let position = cell.position.get();
+            let selected = view_state.selection.is_some_and(|(anchor, focus)| {
+                (anchor.x.min(focus.x)..=anchor.x.max(focus.x)).contains(&position.x)
+                    && (anchor.y.min(focus.y)..=anchor.y.max(focus.y)).contains(&position.y)
+            });
+            if selected {
+                cell.surface.add_css_class(CLASS_SELECTED);
+            } else {
+                cell.surface.remove_css_class(CLASS_SELECTED);
             }
-        });
+        }
+        if let Some(board) = &view_state.canvas {
+            board.canvas.queue_draw();
+        }
     }
 
     fn rebuild_grid(state: &Rc<RefCell<ViewState>>) {
@@ -461,16 +1452,12 @@ impl TableView {
             return;
         };
 
-        {
-            let mut view_state = state.borrow_mut();
-            if let Some(previous) = view_state.selected_surface.take() {
-                previous.remove_css_class(CLASS_SELECTED);
-            }
-        }
+        state.borrow_mut().selection = None;
 
         let grid = Self::build_grid(&table, Rc::clone(state));
         board.set_ratio(Self::table_ratio(&table));
         board.set_child(Some(&grid));
+        Self::apply_board_size(&state.borrow());
     }
 
     fn build_action_button(label: &str) -> Button {
@@ -481,41 +1468,126 @@ impl TableView {
         button
     }
 
-    fn render_all_cells(state: &Rc<RefCell<ViewState>>) {
-        let (render_items, row_buttons, column_buttons, mode) = {
-            let view_state = state.borrow();
-            (
-                view_state
+    /// Re-renders the cells marked dirty since the last call, skipping those that still
+    /// look the same. Headers and edit controls only follow full re-renders, since a
+    /// seat's status never shows on them. The drawn board repaints as a whole.
+    fn render_changed_cells(state: &Rc<RefCell<ViewState>>) {
+        let (render_items, headers, mode, canvas) = {
+            let mut guard = state.borrow_mut();
+            let view_state = &mut *guard;
+            let dirty = std::mem::take(&mut view_state.dirty);
+            let mode = view_state.mode;
+            let table = &view_state.table;
+            let indices: Vec<usize> = if dirty.all {
+                view_state.cell_index = view_state
                     .cells
                     .iter()
-                    .map(|cell| {
-                        let subject = view_state.table.subject_at_owned(cell.position);
-                        let status = view_state.attendance.status_at(cell.position);
-                        (
-                            cell.container.clone(),
-                            cell.surface.clone(),
-                            view_state.mode,
-                            subject,
-                            status,
-                        )
+                    .enumerate()
+                    .map(|(index, cell)| (cell.position.get(), index))
+                    .collect();
+                (0..view_state.cells.len()).collect()
+            } else {
+                dirty
+                    .positions
+                    .iter()
+                    .filter_map(|position| {
+                        let origin = table.cell_origin(*position);
+                        view_state.cell_index.get(&origin).copied()
                     })
-                    .collect::<Vec<_>>(),
-                view_state.row_action_buttons.clone(),
-                view_state.column_action_buttons.clone(),
-                view_state.mode,
-            )
+                    .collect()
+            };
+
+            let mut render_items = Vec::new();
+            for index in indices {
+                let cell = &mut view_state.cells[index];
+                let position = cell.position.get();
+                let note = view_state.attendance.note_at(position);
+                let subject = table.subject_at(position);
+                let status = view_state.attendance.status_at(position);
+                let unchanged = cell.rendered.as_ref().is_some_and(|rendered| {
+                    rendered.mode == mode
+                        && rendered.has_seat_label(table, position)
+                        && rendered.note.as_deref() == note
+                        && rendered.subject.as_ref() == subject
+                        && rendered.status == status
+                });
+                if unchanged {
+                    continue;
+                }
+
+                let rendered = RenderedCell {
+                    mode,
+                    position,
+                    row_label: table.row_label(position.y).map(str::to_owned),
+                    column_label: table.column_label(position.x).map(str::to_owned),
+                    seat: table.seat_label(position),
+                    note: note.map(str::to_owned),
+                    subject: subject.cloned(),
+                    status,
+                };
+                render_items.push((
+                    cell.container.clone(),
+                    cell.surface.clone(),
+                    rendered.clone(),
+                ));
+                cell.rendered = Some(rendered);
+            }
+
+            let headers = dirty.all.then(|| {
+                let row_headers = view_state
+                    .row_headers
+                    .iter()
+                    .map(|header| {
+                        let label = table.row_label(header.index()).map(str::to_owned);
+                        (header.clone(), label)
+                    })
+                    .collect::<Vec<_>>();
+                let column_headers = view_state
+                    .column_headers
+                    .iter()
+                    .map(|header| {
+                        let label = table.column_label(header.index()).map(str::to_owned);
+                        (header.clone(), label)
+                    })
+                    .collect::<Vec<_>>();
+                (
+                    [
+                        (row_headers, table.has_row_labels()),
+                        (column_headers, table.has_column_labels()),
+                    ],
+                    view_state.row_action_buttons.clone(),
+                    view_state.column_action_buttons.clone(),
+                )
+            });
+            (render_items, headers, mode, view_state.canvas.clone())
         };
 
-        for (container, surface, mode, subject, status) in render_items {
-            TableCell::render_to(&container, &surface, mode, subject.as_ref(), status);
+        for (container, surface, rendered) in render_items {
+            TableCell::render_to(
+                &container,
+                &surface,
+                &rendered.seat,
+                rendered.note.as_deref(),
+                rendered.mode,
+                rendered.subject.as_ref(),
+                rendered.status,
+            );
         }
+        if let Some((headers, row_buttons, column_buttons)) = headers {
+            for (headers, axis_labeled) in headers {
+                for (header, label) in headers {
+                    header.render(mode, label.as_deref(), axis_labeled);
+                }
+            }
 
-        let controls_visible = mode == AppMode::Edit;
-        for button in row_buttons {
-            button.set_visible(controls_visible);
+            let controls_visible = mode == AppMode::Edit;
+            for button in row_buttons.into_iter().chain(column_buttons) {
+                button.set_visible(controls_visible);
+            }
         }
-        for button in column_buttons {
-            button.set_visible(controls_visible);
+        if let Some(board) = canvas {
+            Self::describe_canvas_focus(board.canvas.widget().upcast_ref(), state);
+            board.canvas.queue_draw();
         }
     }
 
@@ -0,0 +1,174 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::prelude::*;
+use gtk4::{cairo, glib, DrawingArea};
+
+use crate::core::{
+    is_slipping, AttendanceStatistics, AttendanceStatus, Rgba, SessionCompletion, Theme,
+};
+use crate::ui::ui_styles::{connect_theme_changed, current_theme};
+
+const CHART_HEIGHT: i32 = 150;
+const TODAY_BAR_HEIGHT: f64 = 18.0;
+const CAPTION_HEIGHT: f64 = 16.0;
+const GAP: f64 = 6.0;
+
+#[derive(Default)]
+struct TrendData {
+    sessions: Vec<SessionCompletion>,
+    today: AttendanceStatistics,
+}
+
+/// Completion bars for recent sessions above a stacked bar of today's statuses.
+///
+/// The session bars turn amber when attendance is slipping.
+#[derive(Clone)]
+pub struct TrendChart {
+    area: DrawingArea,
+    data: Rc<RefCell<TrendData>>,
+}
+
+impl TrendChart {
+    pub fn new() -> Self {
+        let area = DrawingArea::new();
+        area.set_content_height(CHART_HEIGHT);
+        area.set_hexpand(true);
+
+        let data = Rc::new(RefCell::new(TrendData::default()));
+        {
+            let data = Rc::clone(&data);
+            area.set_draw_func(move |_, cr, width, height| {
+                if let Err(error) =
+                    Self::draw(cr, &data.borrow(), f64::from(width), f64::from(height))
+                {
+                    eprintln!("failed to draw attendance trend: {error}");
+                }
+            });
+        }
+        let weak_area = area.downgrade();
+        connect_theme_changed(move || match weak_area.upgrade() {
+            Some(area) => {
+                area.queue_draw();
+                glib::ControlFlow::Continue
+            }
+            None => glib::ControlFlow::Break,
+        });
+
+        Self { area, data }
+    }
+
+    pub fn widget(&self) -> &DrawingArea {
+        &self.area
+    }
+
+    pub fn set_sessions(&self, sessions: Vec<SessionCompletion>) {
+        self.data.borrow_mut().sessions = sessions;
+        self.area.queue_draw();
+    }
+
+    pub fn set_today(&self, today: AttendanceStatistics) {
+        self.data.borrow_mut().today = today;
+        self.area.queue_draw();
+    }
+
+    fn draw(
+        cr: &cairo::Context,
+        data: &TrendData,
+        width: f64,
+        height: f64,
+    ) -> Result<(), cairo::Error> {
+        cr.select_font_face("Sans", cairo::FontSlant::Normal, cairo::FontWeight::Normal);
+        cr.set_font_size(10.0);
+
+        let theme = current_theme();
+        let bars_bottom = height - TODAY_BAR_HEIGHT - CAPTION_HEIGHT - GAP;
+        Self::draw_sessions(cr, &theme, &data.sessions, width, bars_bottom)?;
+        Self::draw_today(cr, &theme, data.today, height - TODAY_BAR_HEIGHT, width)
+    }
+
+    fn draw_sessions(
+        cr: &cairo::Context,
+        theme: &Theme,
+        sessions: &[SessionCompletion],
+        width: f64,
+        bottom: f64,
+    ) -> Result<(), cairo::Error> {
+        let plot_top = CAPTION_HEIGHT;
+        let plot_height = (bottom - plot_top).max(1.0);
+
+        Self::set_source(cr, theme.secondary_text);
+        cr.move_to(0.0, CAPTION_HEIGHT - 4.0);
+        if sessions.is_empty() {
+            return cr.show_text("no saved sessions yet");
+        }
+        let slipping = is_slipping(sessions);
+        let caption = if slipping {
+            format!("last {} sessions · attendance is slipping", sessions.len())
+        } else {
+            format!("last {} sessions", sessions.len())
+        };
+        cr.show_text(&caption)?;
+
+        // Baseline and 100% guide.
+        Self::set_source(cr, theme.panel_border);
+        cr.set_line_width(1.0);
+        cr.move_to(0.0, bottom + 0.5);
+        cr.line_to(width, bottom + 0.5);
+        cr.move_to(0.0, plot_top + 0.5);
+        cr.line_to(width, plot_top + 0.5);
+        cr.stroke()?;
+
+        let slot = width / sessions.len() as f64;
+        let bar_width = (slot * 0.7).max(2.0);
+        for (index, session) in sessions.iter().enumerate() {
+            let bar_height = plot_height * f64::from(session.percent.min(100)) / 100.0;
+            let x = slot * index as f64 + (slot - bar_width) / 2.0;
+            if slipping {
+                Self::set_source(cr, theme.highlighted);
+            } else {
+                Self::set_source(cr, theme.selected);
+            }
+            cr.rectangle(x, bottom - bar_height, bar_width, bar_height);
+            cr.fill()?;
+
+            if bar_width >= 22.0 {
+                Self::set_source(cr, theme.text);
+                cr.move_to(x, (bottom - bar_height - 2.0).max(plot_top + 10.0));
+                cr.show_text(&format!("{}%", session.percent))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn draw_today(
+        cr: &cairo::Context,
+        theme: &Theme,
+        today: AttendanceStatistics,
+        top: f64,
+        width: f64,
+    ) -> Result<(), cairo::Error> {
+        let total = f64::from(today.active_total.max(1));
+        let mut x = 0.0;
+        for (status, count) in [
+            (AttendanceStatus::Checked, today.checked),
+            (AttendanceStatus::Marked, today.marked),
+            (AttendanceStatus::Unchecked, today.unchecked),
+        ] {
+            let segment = width * f64::from(count) / total;
+            Self::set_source(cr, theme.status(status).background.with_alpha(1.0));
+            cr.rectangle(x, top, segment, TODAY_BAR_HEIGHT);
+            cr.fill()?;
+            x += segment;
+        }
+
+        Self::set_source(cr, theme.text);
+        cr.move_to(4.0, top + TODAY_BAR_HEIGHT - 5.0);
+        cr.show_text(&format!("today {}%", today.completed_ratio_percent()))
+    }
+
+    fn set_source(cr: &cairo::Context, color: Rgba) {
+        let (red, green, blue, alpha) = color.fractions();
+        cr.set_source_rgba(red, green, blue, alpha);
+    }
+}
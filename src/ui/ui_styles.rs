@@ -1,11 +1,26 @@
 use std::cell::RefCell;
 
-use gtk4::{gdk, CssProvider, STYLE_PROVIDER_PRIORITY_APPLICATION};
+use gtk4::{gdk, glib, CssProvider, Settings, STYLE_PROVIDER_PRIORITY_APPLICATION};
 
-use crate::core::AttendanceStatus;
+use crate::core::{AttendanceStatus, Rgba, SeatColors, Theme};
+
+/// Seat name size at zoom 1.0, matching `.cell-surface`.
+const BOARD_FONT_SIZE: f64 = 13.0;
+/// Names never shrink below this when zoomed out.
+const MIN_BOARD_FONT_SIZE: f64 = 11.0;
+const MAX_BOARD_FONT_SIZE: f64 = 44.0;
 
 thread_local! {
     static GLOBAL_STYLE_PROVIDER: RefCell<Option<CssProvider>> = const { RefCell::new(None) };
+    static BOARD_SCALE_PROVIDER: RefCell<Option<CssProvider>> = const { RefCell::new(None) };
+    static CURRENT_THEME: RefCell<Theme> = const { RefCell::new(Theme::LIGHT) };
+    static THEME_LISTENERS: RefCell<Vec<Box<dyn Fn() -> glib::ControlFlow>>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// Seat name size in pixels for a board scale, kept legible when zoomed out.
+pub fn board_font_size(scale: f64) -> f64 {
+    (BOARD_FONT_SIZE * scale).clamp(MIN_BOARD_FONT_SIZE, MAX_BOARD_FONT_SIZE)
 }
 
 /// Installs application-level CSS once per UI thread and keeps it alive.
@@ -16,7 +31,7 @@ pub fn ensure_ui_styles() {
         }
 
         let provider = CssProvider::new();
-        provider.load_from_data(&build_ui_css());
+        provider.load_from_data(&build_ui_css(&current_theme()));
 
         if let Some(display) = gdk::Display::default() {
             gtk4::style_context_add_provider_for_display(
@@ -30,111 +45,279 @@ pub fn ensure_ui_styles() {
     });
 }
 
-fn build_ui_css() -> String {
-    let mut css = String::from(
-        "
-        window {
-            background-color: rgb(208, 208, 208);
+/// Colors in use, for widgets that draw themselves.
+pub fn current_theme() -> Theme {
+    CURRENT_THEME.with(|theme| *theme.borrow())
+}
+
+/// Switches every color in the app, including GTK's own widgets for dark themes.
+pub fn apply_theme(theme: Theme) {
+    if current_theme() == theme {
+        return;
+    }
+    CURRENT_THEME.with(|current| *current.borrow_mut() = theme);
+    if let Some(settings) = Settings::default() {
+        settings.set_gtk_application_prefer_dark_theme(theme.dark);
+    }
+    GLOBAL_STYLE_PROVIDER.with(|slot| {
+        if let Some(provider) = slot.borrow().as_ref() {
+            provider.load_from_data(&build_ui_css(&theme));
         }
-        .app-root {
+    });
+
+    let listeners = THEME_LISTENERS.with(|listeners| std::mem::take(&mut *listeners.borrow_mut()));
+    let kept: Vec<_> = listeners
+        .into_iter()
+        .filter(|listener| listener() == glib::ControlFlow::Continue)
+        .collect();
+    THEME_LISTENERS.with(|listeners| listeners.borrow_mut().splice(0..0, kept));
+}
+
+/// Called after each theme switch until it returns `ControlFlow::Break`, e.g. to
+/// redraw a drawing area, stopping once the widget is gone.
+pub fn connect_theme_changed<F>(listener: F)
+where
+    F: Fn() -> glib::ControlFlow + 'static,
+{
+    THEME_LISTENERS.with(|listeners| listeners.borrow_mut().push(Box::new(listener)));
+}
+
+/// Scales seat names and padding with the board zoom, keeping names legible.
+pub fn set_board_font_scale(scale: f64) {
+    BOARD_SCALE_PROVIDER.with(|slot| {
+        let mut slot = slot.borrow_mut();
+        let provider = slot.get_or_insert_with(|| {
+            let provider = CssProvider::new();
+            if let Some(display) = gdk::Display::default() {
+                gtk4::style_context_add_provider_for_display(
+                    &display,
+                    &provider,
+                    STYLE_PROVIDER_PRIORITY_APPLICATION + 1,
+                );
+            }
+            provider
+        });
+
+        let font_size = board_font_size(scale);
+        let padding = (scale * 8.0).clamp(2.0, 24.0);
+        provider.load_from_data(&format!(
+            ".table-board .cell-surface {{ font-size: {font_size:.1}px; padding: {padding:.0}px {:.0}px; }}",
+            padding * 1.25
+        ));
+    });
+}
+
+fn build_ui_css(theme: &Theme) -> String {
+    let window_background = css_color(theme.window_background);
+    let panel_background = css_color(theme.panel_background);
+    let panel_border = css_color(theme.panel_border);
+    let text = css_color(theme.text);
+    let secondary_text = css_color(theme.secondary_text);
+    let muted_text = css_color(theme.muted_text);
+    let success_text = css_color(theme.success_text);
+    let error_text = css_color(theme.error_text);
+    let warning_background = css_color(theme.warning_background);
+    let warning_border = css_color(theme.warning_border);
+    let error_background = css_color(theme.error_background);
+    let error_border = css_color(theme.error_border);
+    let selected = css_color(theme.selected);
+    let selected_glow = css_color(theme.selected.with_alpha(0.25));
+    let highlighted = css_color(theme.highlighted);
+    let highlighted_glow = css_color(theme.highlighted.with_alpha(0.45));
+
+    let mut css = format!(
+        "
+        window {{
+            background-color: {window_background};
+        }}
+        .app-root {{
             background-color: transparent;
             border-radius: 24px;
-        }
-        .statistics-panel {
+        }}
+        .statistics-panel {{
             margin-bottom: 8px;
             padding: 10px 12px;
             border-radius: 12px;
-            border: 1px solid rgba(148, 163, 184, 0.4);
-            background-color: rgba(248, 250, 252, 0.8);
-        }
-        .statistics-title {
-            color: #64748b;
+            border: 1px solid {panel_border};
+            background-color: {panel_background};
+        }}
+        .statistics-title {{
+            color: {muted_text};
             font-size: 11px;
             font-weight: 760;
-        }
-        .statistics-summary {
-            color: #0f172a;
+        }}
+        .statistics-summary {{
+            color: {text};
             font-size: 15px;
             font-weight: 760;
-        }
-        .statistics-detail {
-            color: #334155;
+        }}
+        .statistics-detail {{
+            color: {secondary_text};
             font-size: 12px;
-        }
-        .board-shell {
+        }}
+        .statistics-groups {{
+            color: {secondary_text};
+            font-size: 12px;
+        }}
+        .diagnostics-banner {{
+            padding: 8px 12px;
+            border-radius: 12px;
+            border: 1px solid {warning_border};
+            background-color: {warning_background};
+        }}
+        .diagnostics-banner.has-errors {{
+            border-color: {error_border};
+            background-color: {error_background};
+        }}
+        .diagnostics-title {{
+            color: {text};
+            font-size: 12px;
+            font-weight: 760;
+        }}
+        .diagnostics-detail {{
+            color: {secondary_text};
+            font-family: monospace;
+            font-size: 11px;
+        }}
+        .self-checkin-pin {{
+            color: {text};
+            font-size: 15px;
+            font-family: monospace;
+        }}
+        .self-checkin-address {{
+            color: {secondary_text};
+            font-size: 12px;
+        }}
+        .scan-feedback {{
+            color: {secondary_text};
+            font-size: 12px;
+        }}
+        .scan-feedback.scan-ok {{
+            color: {success_text};
+        }}
+        .scan-feedback.scan-attention {{
+            color: {error_text};
+            font-weight: 760;
+        }}
+        .board-shell {{
             padding: 0px;
-        }
-        .table-board {
+        }}
+        .table-board {{
             background-color: transparent;
-        }
-        .table-grid {
+        }}
+        .table-grid {{
             margin: 0;
-        }
-        .table-cell {
+        }}
+        .table-header-label {{
+            color: {secondary_text};
+            font-size: 12px;
+            font-weight: 600;
+            padding: 2px 6px;
+        }}
+        .table-cell {{
             margin: 0;
             padding: 0;
-        }
-        .cell-surface {
-            color: #0f172a;
+        }}
+        .cell-surface {{
+            color: {text};
             border: 2px solid transparent;
             padding: 8px 10px;
             font-size: 13px;
             font-weight: 620;
             transition: border-color 180ms ease, box-shadow 180ms ease, transform 180ms ease;
-        }
-        .cell-surface.selected {
-            border-color: #0ea5e9;
-            box-shadow: 0 0 0 2px rgba(14, 165, 233, 0.25);
+        }}
+        .cell-surface.selected {{
+            border-color: {selected};
+            box-shadow: 0 0 0 2px {selected_glow};
             transform: translateY(-1px);
-        }
-        .cell-surface.edit-pending {
-            background-color: rgb(226, 232, 240);
-            border-color: rgb(148, 163, 184);
-            color: rgb(51, 65, 85);
+        }}
+        .cell-surface.edit-pending {{
             box-shadow: none;
-        }
-        ",
+        }}
+        "
     );
 
+    push_seat_colors(&mut css, "edit-pending", theme.edit_pending);
+    for status in AttendanceStatus::ALL {
+        push_seat_colors(&mut css, status.css_class(), theme.status(status));
+    }
+    push_seat_colors(&mut css, "blocked", theme.blocked);
     for status in AttendanceStatus::ALL {
-        let (r, g, b) = status.background_rgb();
         css.push_str(&format!(
-            "
-            .cell-surface.{} {{
-                background-color: rgba({}, {}, {}, {});
-                border-color: rgba({}, {}, {}, 0.55);
-                color: {};
-            }}
-            ",
+            ".cell-surface.{} {{ border-style: {}; }}\n",
             status.css_class(),
-            r,
-            g,
-            b,
-            status.background_alpha(),
-            r,
-            g,
-            b,
-            status.foreground_color(),
+            border_style(status)
         ));
     }
 
-    css.push_str(
+    css.push_str(&format!(
         "
-        .cell-surface.blocked {
-            background-color: rgb(71, 85, 105);
-            border-color: rgb(51, 65, 75);
-            color: rgb(203, 213, 225);
-        }
+        .cell-surface.muted {{
+            color: {muted_text};
+        }}
+        .table-cell:focus-visible > .cell-surface {{
+            outline: 2px dashed {text};
+            outline-offset: -5px;
+        }}
         .table-cell.transparent,
-        .table-cell .cell-surface.transparent {
+        .table-cell .cell-surface.transparent {{
             background-color: transparent;
             border-color: transparent;
             color: transparent;
             box-shadow: none;
             padding: 0;
-        }
-        ",
-    );
+        }}
+        .cell-surface.highlighted {{
+            border-color: {highlighted};
+            box-shadow: 0 0 0 3px {highlighted_glow};
+        }}
+        .picker-result {{
+            color: {text};
+            font-size: 15px;
+            font-weight: 760;
+        }}
+        .roll-call-name {{
+            color: {text};
+            font-size: 40px;
+            font-weight: 800;
+        }}
+        .roll-call-progress {{
+            color: {muted_text};
+            font-size: 12px;
+        }}
+        "
+    ));
 
     css
 }
+
+fn push_seat_colors(css: &mut String, class: &str, colors: SeatColors) {
+    css.push_str(&format!(
+        "
+        .cell-surface.{class} {{
+            background-color: {};
+            border-color: {};
+            color: {};
+        }}
+        ",
+        css_color(colors.background),
+        css_color(colors.border),
+        css_color(colors.foreground),
+    ));
+}
+
+/// Tells statuses apart without their colors; the drawn board uses matching dashes.
+fn border_style(status: AttendanceStatus) -> &'static str {
+    match status {
+        AttendanceStatus::Checked => "solid",
+        AttendanceStatus::Unchecked => "dashed",
+        AttendanceStatus::Marked => "dotted",
+    }
+}
+
+fn css_color(color: Rgba) -> String {
+    format!(
+        "rgba({}, {}, {}, {:.3})",
+        color.red, color.green, color.blue, color.alpha
+    )
+}
@@ -1,4 +1,5 @@
 use gtk4::gdk;
+use gtk4::gio;
 use gtk4::prelude::*;
 
 pub fn write_text_to_clipboard(text: &str) -> Result<(), &'static str> {
@@ -10,3 +11,21 @@ pub fn write_text_to_clipboard(text: &str) -> Result<(), &'static str> {
     clipboard.set_text(text);
     Ok(())
 }
+
+/// Reads clipboard text asynchronously; `on_text` only runs when text is available.
+pub fn read_text_from_clipboard<F>(on_text: F) -> Result<(), &'static str>
+where
+    F: FnOnce(String) + 'static,
+{
+    let Some(display) = gdk::Display::default() else {
+        return Err("unable access to system display");
+    };
+
+    let clipboard = display.clipboard();
+    clipboard.read_text_async(None::<&gio::Cancellable>, move |result| match result {
+        Ok(Some(text)) => on_text(text.to_string()),
+        Ok(None) => eprintln!("clipboard does not contain text"),
+        Err(error) => eprintln!("failed to read clipboard: {error}"),
+    });
+    Ok(())
+}
@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, TcpListener, TcpStream, UdpSocket};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const MAX_BODY_BYTES: usize = 64 * 1024;
+/// Cap on the request line and headers together.
+const MAX_HEAD_BYTES: u64 = 8 * 1024;
+/// Connections handled at once; further ones are closed right away.
+const MAX_CONNECTIONS: usize = 32;
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Minimal HTTP/1.1 request: enough for small local JSON and form endpoints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: String,
+    /// Request path without the query string.
+    pub path: String,
+    pub query: String,
+    /// Header names are lowercased.
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+    /// Address of the connecting client, when known.
+    pub peer: Option<IpAddr>,
+}
+
+impl HttpRequest {
+    pub fn read_from(stream: &mut impl Read) -> io::Result<Self> {
+        let mut reader = BufReader::new(stream);
+        let mut head = reader.by_ref().take(MAX_HEAD_BYTES);
+        let mut request_line = String::new();
+        read_head_line(&mut head, &mut request_line)?;
+        let mut parts = request_line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "malformed request line",
+            ));
+        };
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+        let mut headers = HashMap::new();
+        loop {
+            let mut line = String::new();
+            if read_head_line(&mut head, &mut line)? == 0 {
+                break;
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_owned());
+            }
+        }
+
+        let length = headers
+            .get("content-length")
+            .and_then(|value| value.parse::<usize>().ok())
+            .unwrap_or(0);
+        if length > MAX_BODY_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request body too large",
+            ));
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+
+        Ok(Self {
+            method: method.to_owned(),
+            path: path.to_owned(),
+            query: query.to_owned(),
+            headers,
+            body,
+            peer: None,
+        })
+    }
+
+    pub fn body_text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// Fields of an `application/x-www-form-urlencoded` body.
+    pub fn form_fields(&self) -> HashMap<String, String> {
+        parse_form_urlencoded(&self.body_text())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn json(status: u16, value: &serde_json::Value) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body: value.to_string().into_bytes(),
+        }
+    }
+
+    pub fn text(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: body.into().into_bytes(),
+        }
+    }
+
+    pub fn html(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: "text/html; charset=utf-8",
+            body: body.into().into_bytes(),
+        }
+    }
+
+    pub fn write_to(&self, stream: &mut impl Write) -> io::Result<()> {
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+            self.status,
+            reason_phrase(self.status),
+            self.content_type,
+            self.body.len()
+        )?;
+        stream.write_all(&self.body)?;
+        stream.flush()
+    }
+}
+
+/// Accepts connections on a background thread, one short-lived thread per request.
+///
+/// At most [`MAX_CONNECTIONS`] are handled at once, so clients on the network can't
+/// exhaust threads or file descriptors; connections over the cap are closed unanswered.
+pub fn serve<F>(listener: TcpListener, handler: F)
+where
+    F: Fn(HttpRequest) -> HttpResponse + Send + Sync + 'static,
+{
+    let handler = Arc::new(handler);
+    let active = Arc::new(AtomicUsize::new(0));
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let Some(slot) = ConnectionSlot::acquire(&active) else {
+                continue;
+            };
+            let handler = Arc::clone(&handler);
+            thread::spawn(move || {
+                let _slot = slot;
+                if let Err(error) = handle_connection(stream, handler.as_ref()) {
+                    eprintln!("http connection failed: {error}");
+                }
+            });
+        }
+    });
+}
+
+/// Address other machines on the local network can most likely reach us on.
+///
+/// Connecting a UDP socket only selects a route; no packet is sent.
+pub fn local_network_address() -> Option<IpAddr> {
+    let socket = UdpSocket::bind(("0.0.0.0", 0)).ok()?;
+    socket.connect(("192.0.2.1", 80)).ok()?;
+    let address = socket.local_addr().ok()?.ip();
+    (!address.is_unspecified()).then_some(address)
+}
+
+pub fn parse_form_urlencoded(text: &str) -> HashMap<String, String> {
+    text.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(name), percent_decode(value))
+        })
+        .collect()
+}
+
+/// One of the [`MAX_CONNECTIONS`] connections being handled, released on drop.
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl ConnectionSlot {
+    fn acquire(active: &Arc<AtomicUsize>) -> Option<Self> {
+        active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                (count < MAX_CONNECTIONS).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| Self(Arc::clone(active)))
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Reads one line of the request head, failing once the head outgrows its cap.
+fn read_head_line(head: &mut io::Take<impl BufRead>, line: &mut String) -> io::Result<usize> {
+    let read = head.read_line(line)?;
+    if head.limit() == 0 && !line.ends_with('\n') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "request headers too large",
+        ));
+    }
+    Ok(read)
+}
+
+fn handle_connection<F>(mut stream: TcpStream, handler: &F) -> io::Result<()>
+where
+    F: Fn(HttpRequest) -> HttpResponse,
+{
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let peer = stream.peer_addr().ok().map(|address| address.ip());
+    let response = match HttpRequest::read_from(&mut stream) {
+        Ok(request) => handler(HttpRequest { peer, ..request }),
+        Err(error) => HttpResponse::text(400, error.to_string()),
+    };
+    response.write_to(&mut stream)
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        415 => "Unsupported Media Type",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "",
+    }
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let byte = bytes
+                    .get(index + 1..index + 3)
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                match byte {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        index += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
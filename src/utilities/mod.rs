@@ -1,5 +1,7 @@
 mod clipboard;
+mod http;
 mod time;
 
-pub use clipboard::write_text_to_clipboard;
+pub use clipboard::{read_text_from_clipboard, write_text_to_clipboard};
+pub use http::{local_network_address, parse_form_urlencoded, serve, HttpRequest, HttpResponse};
 pub use time::SystemTimeExt;